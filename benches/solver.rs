@@ -0,0 +1,33 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use sorting_game::game::{Game, GameConfig};
+use std::hint::black_box;
+
+// (name, capacity, kinds, empty stacks, seed). Fixed seeds keep the boards identical between
+// runs, so timings stay comparable.
+const STAGES: [(&str, usize, usize, usize, u64); 3] = [
+    ("easy", 3, 3, 1, 7),
+    ("medium", 4, 4, 2, 42),
+    ("hard", 5, 7, 2, 1234),
+];
+
+fn bench_solve(c: &mut Criterion) {
+    let mut group = c.benchmark_group("solve");
+    group.sample_size(10);
+    for (name, capacity, kinds, empty_stacks, seed) in STAGES {
+        let config: GameConfig =
+            GameConfig::new(capacity, kinds, empty_stacks).expect("the bench layouts are valid");
+        let game: Game = Game::generate(&config, seed);
+        // Checked up front, so a stage turning unsolvable fails instead of timing a dead end.
+        assert!(
+            game.solve().is_some(),
+            "the {} stage (seed {}) can no longer be solved",
+            name,
+            seed
+        );
+        group.bench_function(name, |b| b.iter(|| black_box(&game).solve()));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_solve);
+criterion_main!(benches);
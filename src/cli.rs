@@ -0,0 +1,295 @@
+use sorting_game::game::{CostModel, Palette};
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage:
+  sorting-game                                     choose from the stage menu
+  sorting-game --stage <file>                      play a single stage file
+  sorting-game --seed <n> [--kinds <n>] [--capacity <n>] [--empty <n>]
+                                                   play a generated stage
+  sorting-game --help                              show this message
+
+Options:
+  --ascii                 draw the board in plain ASCII, also used when NO_COLOR is set
+  --palette <name>        color set for the kinds: standard (default) or colorblind,
+                          which also draws a distinct symbol on every unit;
+                          colors can be overridden in ~/.sorting-game/palette.conf
+  --plain-empty           draw empty stacks like any other, without the dotted slots
+  --compact               draw a run of identical units once, with its length, e.g. Ax3
+  --explicit-moves        always ask for the target stack, even if only one would take the units
+  --fast-forward          after each move, play any moves that leave nothing to decide
+  --undo-limit <n>        allow at most n undos per stage, unless the stage sets its own limit
+  --quiet                 no bell or animation when a stage is sorted
+  --two-player            two players take turns on the same board, whoever sorts the
+                          last kind wins; undo is off
+  --face-down             only the top unit of every stack shows, the ones beneath are
+                          revealed as they reach the top; hints are off while any are hidden
+  --fast                  skip the intro screen before every stage
+  --pour-cost <n>         effort every pour adds to the total shown on completion (default 1)
+  --unit-cost <n>         effort added for every unit a pour carries (default 1)
+  --no-sort               list the stages in their own order, instead of easy to hard
+  --check-solvable        solve every stage up front and leave out those with no solution,
+                          slow with many large stages
+
+Keys can be rebound in ~/.sorting-game/keys.conf, one '<action> = <key>' per line";
+
+const DEFAULT_KINDS: usize = 5;
+const DEFAULT_CAPACITY: usize = 4;
+const DEFAULT_EMPTY_STACKS: usize = 2;
+
+#[derive(PartialEq, Debug)]
+pub struct Args {
+    pub command: Command,
+    pub ascii: bool,
+    pub plain_empty: bool,
+    pub compact: bool,
+    pub palette: Palette,
+    pub explicit_moves: bool,
+    pub fast_forward: bool,
+    pub undo_limit: Option<usize>,
+    pub quiet: bool,
+    pub two_player: bool,
+    pub no_sort: bool,
+    pub check_solvable: bool,
+    pub cost_model: CostModel,
+    pub face_down: bool,
+    pub fast: bool,
+    pub dev: bool,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Command {
+    Menu,
+    Help,
+    Stage(PathBuf),
+    Generate {
+        seed: u64,
+        kinds: usize,
+        capacity: usize,
+        empty_stacks: usize,
+    },
+}
+
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Args, String> {
+    let mut ascii: bool = false;
+    let mut plain_empty: bool = false;
+    let mut compact: bool = false;
+    let mut palette: Palette = Palette::standard();
+    let mut explicit_moves: bool = false;
+    let mut fast_forward: bool = false;
+    let mut undo_limit: Option<usize> = None;
+    let mut quiet: bool = false;
+    let mut two_player: bool = false;
+    let mut no_sort: bool = false;
+    let mut check_solvable: bool = false;
+    let mut cost_model: CostModel = CostModel::default();
+    let mut face_down: bool = false;
+    let mut fast: bool = false;
+    let mut dev: bool = false;
+    let mut stage: Option<PathBuf> = None;
+    let mut seed: Option<u64> = None;
+    let mut kinds: Option<usize> = None;
+    let mut capacity: Option<usize> = None;
+    let mut empty_stacks: Option<usize> = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                return Ok(Args {
+                    command: Command::Help,
+                    ascii,
+                    plain_empty,
+                    compact,
+                    palette,
+                    explicit_moves,
+                    fast_forward,
+                    undo_limit,
+                    quiet,
+                    two_player,
+                    no_sort,
+                    check_solvable,
+                    cost_model,
+                    face_down,
+                    fast,
+                    dev,
+                })
+            }
+            "--ascii" => ascii = true,
+            "--plain-empty" => plain_empty = true,
+            "--compact" => compact = true,
+            "--explicit-moves" => explicit_moves = true,
+            "--fast-forward" => fast_forward = true,
+            "--quiet" => quiet = true,
+            "--two-player" => two_player = true,
+            "--no-sort" => no_sort = true,
+            "--check-solvable" => check_solvable = true,
+            "--face-down" => face_down = true,
+            "--fast" => fast = true,
+            "--dev" => dev = true, // Left out of the usage, it's only for testing stages.
+            "--undo-limit" => undo_limit = Some(number_of(&arg, args.next())?),
+            "--pour-cost" => cost_model.per_pour = number_of(&arg, args.next())?,
+            "--unit-cost" => cost_model.per_unit = number_of(&arg, args.next())?,
+            "--palette" => {
+                let name: String = value_of(&arg, args.next())?;
+                palette = Palette::from_name(&name)
+                    .ok_or_else(|| format!("unknown palette '{}'", name))?;
+            }
+            "--stage" => stage = Some(PathBuf::from(value_of(&arg, args.next())?)),
+            "--seed" => seed = Some(number_of(&arg, args.next())?),
+            "--kinds" => kinds = Some(number_of(&arg, args.next())?),
+            "--capacity" => capacity = Some(number_of(&arg, args.next())?),
+            "--empty" => empty_stacks = Some(number_of(&arg, args.next())?),
+            _ => return Err(format!("unknown argument '{}'", arg)),
+        }
+    }
+
+    let shapes_generated: bool = kinds.is_some() || capacity.is_some() || empty_stacks.is_some();
+    let command: Command = match (stage, seed) {
+        (Some(_), Some(_)) => return Err("--stage and --seed can't be combined".to_string()),
+        (Some(_), None) if shapes_generated => {
+            return Err("--kinds, --capacity and --empty only apply to --seed".to_string())
+        }
+        (Some(path), None) => Command::Stage(path),
+        (None, Some(seed)) => Command::Generate {
+            seed,
+            kinds: kinds.unwrap_or(DEFAULT_KINDS),
+            capacity: capacity.unwrap_or(DEFAULT_CAPACITY),
+            empty_stacks: empty_stacks.unwrap_or(DEFAULT_EMPTY_STACKS),
+        },
+        (None, None) if shapes_generated => {
+            return Err("--kinds, --capacity and --empty need a --seed".to_string())
+        }
+        (None, None) => Command::Menu,
+    };
+    Ok(Args {
+        command,
+        ascii,
+        plain_empty,
+        compact,
+        palette,
+        explicit_moves,
+        fast_forward,
+        undo_limit,
+        quiet,
+        two_player,
+        no_sort,
+        check_solvable,
+        cost_model,
+        face_down,
+        fast,
+        dev,
+    })
+}
+
+fn value_of(flag: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("{} needs a value", flag))
+}
+
+fn number_of<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    let value: String = value_of(flag, value)?;
+    value
+        .parse::<T>()
+        .map_err(|_| format!("{} expects a number, got '{}'", flag, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        parse_args(args.iter().map(|arg| arg.to_string())).map(|args| args.command)
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(parse(&[]), Ok(Command::Menu));
+        assert_eq!(parse(&["--help"]), Ok(Command::Help));
+        assert_eq!(
+            parse(&["--stage", "levels/one.stage"]),
+            Ok(Command::Stage(PathBuf::from("levels/one.stage")))
+        );
+        assert_eq!(
+            parse(&["--seed", "12345", "--kinds", "6", "--capacity", "4"]),
+            Ok(Command::Generate {
+                seed: 12345,
+                kinds: 6,
+                capacity: 4,
+                empty_stacks: DEFAULT_EMPTY_STACKS
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_ascii_flag() {
+        let args: Args =
+            parse_args(["--ascii", "--seed", "3"].iter().map(|arg| arg.to_string())).unwrap();
+        assert!(args.ascii);
+        assert!(matches!(args.command, Command::Generate { seed: 3, .. }));
+        assert!(!parse_args(Vec::new()).unwrap().ascii);
+        assert!(
+            parse_args(["--plain-empty".to_string()])
+                .unwrap()
+                .plain_empty
+        );
+        assert!(parse_args(["--compact".to_string()]).unwrap().compact);
+        assert!(
+            parse_args(["--explicit-moves".to_string()])
+                .unwrap()
+                .explicit_moves
+        );
+        assert!(
+            parse_args(["--fast-forward".to_string()])
+                .unwrap()
+                .fast_forward
+        );
+        assert!(parse_args(["--two-player".to_string()]).unwrap().two_player);
+        assert!(parse_args(["--no-sort".to_string()]).unwrap().no_sort);
+        assert!(
+            parse_args(["--check-solvable".to_string()])
+                .unwrap()
+                .check_solvable
+        );
+        assert!(parse_args(["--face-down".to_string()]).unwrap().face_down);
+        assert!(parse_args(["--fast".to_string()]).unwrap().fast);
+        assert!(parse_args(["--dev".to_string()]).unwrap().dev);
+    }
+
+    #[test]
+    fn test_parse_palette() {
+        let args: Args = parse_args(
+            ["--palette", "colorblind"]
+                .iter()
+                .map(|arg| arg.to_string()),
+        )
+        .unwrap();
+        assert_eq!(args.palette, Palette::colorblind());
+        assert_eq!(parse_args(Vec::new()).unwrap().palette, Palette::standard());
+        assert!(parse(&["--palette", "sepia"]).is_err());
+        assert!(parse(&["--palette"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_cost_model() {
+        let args: Args =
+            parse_args(["--unit-cost", "0"].iter().map(|arg| arg.to_string())).unwrap();
+        assert_eq!(
+            args.cost_model,
+            CostModel {
+                per_pour: 1,
+                per_unit: 0
+            }
+        );
+        assert!(parse(&["--pour-cost", "-1"]).is_err());
+    }
+
+    #[test]
+    fn test_reject_invalid_arguments() {
+        assert!(parse(&["--seed", "1", "--stage", "one.stage"]).is_err());
+        assert!(parse(&["--stage", "one.stage", "--kinds", "3"]).is_err());
+        assert!(parse(&["--kinds", "3"]).is_err());
+        assert!(parse(&["--seed", "many"]).is_err());
+        assert!(parse(&["--seed"]).is_err());
+        assert!(parse(&["--colour"]).is_err());
+    }
+}
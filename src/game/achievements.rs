@@ -0,0 +1,232 @@
+#[cfg(feature = "serde")]
+use crate::game::config;
+use crate::game::daily::Date;
+use crate::game::scores::Leaderboard;
+use crate::game::Game;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+#[cfg(feature = "serde")]
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "serde")]
+const ACHIEVEMENTS_FILE_NAME: &str = "achievements.json";
+const STREAK_DAYS: usize = 7;
+
+// What an achievement is judged on: the stage just completed, with the scores already updated.
+pub struct Progress<'a> {
+    pub stage: &'a Game,
+    pub leaderboard: &'a Leaderboard,
+    pub today: Date,
+}
+
+pub struct Achievement {
+    pub id: &'static str, // Stored in the achievements file, so never renamed.
+    pub name: &'static str,
+    pub description: &'static str,
+    pub unlocks: fn(&Progress) -> bool,
+}
+
+// New achievements only need an entry here.
+pub const ACHIEVEMENTS: [Achievement; 4] = [
+    Achievement {
+        id: "par",
+        name: "On Par",
+        description: "solve a stage in par",
+        unlocks: |progress| {
+            progress
+                .stage
+                .get_par()
+                .is_some_and(|par| progress.stage.get_move_count() <= par)
+        },
+    },
+    Achievement {
+        id: "no_undo",
+        name: "No Regrets",
+        description: "clear a stage without undoing a move",
+        unlocks: |progress| progress.stage.get_undo_count() == 0,
+    },
+    Achievement {
+        id: "all_stages",
+        name: "Completionist",
+        description: "complete every built-in stage",
+        unlocks: |progress| {
+            Game::get_stages()
+                .iter()
+                .all(|stage| progress.leaderboard.get(stage.get_stage_name()).is_some())
+        },
+    },
+    Achievement {
+        id: "week_streak",
+        name: "Creature of Habit",
+        description: "complete the daily challenge 7 days in a row",
+        unlocks: |progress| progress.leaderboard.daily_streak(progress.today) >= STREAK_DAYS,
+    },
+];
+
+#[derive(Default, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Achievements {
+    unlocked: BTreeSet<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))] // Only read when they are stored.
+    path: Option<PathBuf>,
+}
+
+impl Achievements {
+    #[cfg(feature = "serde")]
+    pub fn default_path() -> Option<PathBuf> {
+        config::data_dir().map(|dir| dir.join(ACHIEVEMENTS_FILE_NAME))
+    }
+
+    #[cfg(not(feature = "serde"))]
+    pub fn default_path() -> Option<PathBuf> {
+        None
+    }
+
+    pub fn load() -> Achievements {
+        match Achievements::default_path() {
+            Some(path) => Achievements::load_from_path(&path),
+            None => Achievements::default(),
+        }
+    }
+
+    // Like the scores, an unreadable file starts over rather than failing.
+    #[cfg(feature = "serde")]
+    pub fn load_from_path(path: &Path) -> Achievements {
+        let mut achievements: Achievements = match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|error| {
+                eprintln!(
+                    "Ignoring corrupt achievements file {}: {}",
+                    path.display(),
+                    error
+                );
+                Achievements::default()
+            }),
+            Err(_) => Achievements::default(),
+        };
+        achievements.path = Some(path.to_path_buf());
+        achievements
+    }
+
+    #[cfg(not(feature = "serde"))]
+    pub fn load_from_path(path: &Path) -> Achievements {
+        Achievements {
+            path: Some(path.to_path_buf()),
+            ..Achievements::default()
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn store(&self) -> io::Result<()> {
+        match &self.path {
+            Some(path) => {
+                let json: String = serde_json::to_string_pretty(self)?;
+                config::write_atomically(path, json.as_bytes())
+            }
+            None => Ok(()),
+        }
+    }
+
+    #[cfg(not(feature = "serde"))]
+    pub fn store(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.contains(id)
+    }
+
+    // Unlocks every achievement the progress earns, returning those that weren't already.
+    pub fn unlock_new(&mut self, progress: &Progress) -> Vec<&'static Achievement> {
+        let unlocked: Vec<&'static Achievement> = ACHIEVEMENTS
+            .iter()
+            .filter(|achievement| !self.is_unlocked(achievement.id))
+            .filter(|achievement| (achievement.unlocks)(progress))
+            .collect();
+        for achievement in &unlocked {
+            self.unlocked.insert(achievement.id.to_string());
+        }
+        unlocked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::scores::BestScore;
+
+    fn unlocked_ids(achievements: &mut Achievements, progress: &Progress) -> Vec<&'static str> {
+        achievements
+            .unlock_new(progress)
+            .iter()
+            .map(|achievement| achievement.id)
+            .collect()
+    }
+
+    #[test]
+    fn test_par_and_no_undo_clears() {
+        let mut stage: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        stage.compute_par();
+        stage.run_script(&[(0, 2)]).unwrap(); // A detour, undone.
+        stage.undo_move();
+        stage.run_script(&stage.solve().unwrap()).unwrap();
+        let leaderboard: Leaderboard = Leaderboard::default();
+        let today: Date = Date::new(2026, 10, 14);
+
+        let mut achievements: Achievements = Achievements::default();
+        let progress: Progress = Progress {
+            stage: &stage,
+            leaderboard: &leaderboard,
+            today,
+        };
+        assert_eq!(unlocked_ids(&mut achievements, &progress), vec!["par"]);
+
+        let mut clean: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        clean.run_script(&clean.solve().unwrap()).unwrap();
+        let progress: Progress = Progress {
+            stage: &clean,
+            leaderboard: &leaderboard,
+            today,
+        };
+        // Par was already unlocked, and this stage has none worked out anyway.
+        assert_eq!(unlocked_ids(&mut achievements, &progress), vec!["no_undo"]);
+        assert!(unlocked_ids(&mut achievements, &progress).is_empty());
+    }
+
+    #[test]
+    fn test_week_long_daily_streak() {
+        let mut stage: Game = Game::new_from_vecs(vec![vec![1, 0], vec![1, 0]], None);
+        stage.run_script(&[(0, 1)]).unwrap();
+        stage.undo_move(); // Keeps the no-undo achievement out of the way.
+        stage.run_script(&[(0, 1)]).unwrap();
+        let mut leaderboard: Leaderboard = Leaderboard::default();
+        let score: BestScore = BestScore { moves: 1, time: 5 };
+        for day in 1..=6 {
+            leaderboard.record_daily(Date::new(2026, 10, day), score);
+        }
+        let mut achievements: Achievements = Achievements::default();
+        let mut progress: Progress = Progress {
+            stage: &stage,
+            leaderboard: &leaderboard,
+            today: Date::new(2026, 10, 6),
+        };
+        assert!(unlocked_ids(&mut achievements, &progress).is_empty());
+
+        leaderboard.record_daily(Date::new(2026, 10, 7), score);
+        progress = Progress {
+            stage: &stage,
+            leaderboard: &leaderboard,
+            today: Date::new(2026, 10, 7),
+        };
+        assert_eq!(
+            unlocked_ids(&mut achievements, &progress),
+            vec!["week_streak"]
+        );
+        assert!(achievements.is_unlocked("week_streak"));
+    }
+}
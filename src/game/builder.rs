@@ -0,0 +1,169 @@
+use crate::game::error::BuildError;
+use crate::game::solver::Solvability;
+use crate::game::stack::Stack;
+use crate::game::Game;
+use std::str::FromStr;
+
+#[derive(Clone, Debug)]
+enum StackSource {
+    Built(Stack),
+    Text(String), // Parsed on build, once the capacity is known.
+}
+
+// Stacks written as text take their capacity from `capacity` when it is set, otherwise from
+// the length of the text, the same as in stage files.
+#[derive(Clone, Debug, Default)]
+pub struct GameBuilder {
+    stacks: Vec<StackSource>,
+    stage_name: Option<String>,
+    capacity: Option<usize>,
+    require_solvable: bool,
+}
+
+impl GameBuilder {
+    pub fn new() -> GameBuilder {
+        GameBuilder::default()
+    }
+
+    pub fn stack(mut self, stack: Stack) -> GameBuilder {
+        self.stacks.push(StackSource::Built(stack));
+        self
+    }
+
+    pub fn stack_from_str(mut self, text: &str) -> GameBuilder {
+        self.stacks.push(StackSource::Text(text.to_string()));
+        self
+    }
+
+    pub fn stage_name(mut self, stage_name: impl Into<String>) -> GameBuilder {
+        self.stage_name = Some(stage_name.into());
+        self
+    }
+
+    pub fn capacity(mut self, capacity: usize) -> GameBuilder {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    // Solves the stage on build, which can take a while. Stages too large to settle either way
+    // are let through.
+    pub fn require_solvable(mut self) -> GameBuilder {
+        self.require_solvable = true;
+        self
+    }
+
+    pub fn build(self) -> Result<Game, BuildError> {
+        let mut stacks: Vec<Stack> = Vec::new();
+        for (ind, source) in self.stacks.into_iter().enumerate() {
+            let stack: Stack = match source {
+                StackSource::Built(stack) => stack,
+                StackSource::Text(text) => {
+                    let parsed: Stack = Stack::from_str(&text)
+                        .map_err(|error| BuildError::Stack { stack: ind, error })?;
+                    match self.capacity {
+                        Some(capacity) => {
+                            let ids: Vec<usize> = parsed.iter_unit_ids().collect();
+                            let units: usize = ids.len();
+                            Stack::new_with_capacity(capacity, ids).ok_or(
+                                BuildError::Overfilled {
+                                    stack: ind,
+                                    units,
+                                    capacity,
+                                },
+                            )?
+                        }
+                        None => parsed,
+                    }
+                }
+            };
+            stacks.push(stack);
+        }
+        if stacks.is_empty() {
+            return Err(BuildError::NoStacks);
+        }
+        if let Some((kind, count)) = Game::unsortable_kind(&stacks) {
+            return Err(BuildError::Unsortable { kind, count });
+        }
+        let game: Game = Game::new(stacks, self.stage_name);
+        if self.require_solvable && game.solvability() == Solvability::Unsolvable {
+            return Err(BuildError::Unsolvable);
+        }
+        Ok(game)
+    }
+}
+
+impl Game {
+    pub fn builder() -> GameBuilder {
+        GameBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_a_stage() {
+        let game: Game = Game::builder()
+            .stage_name("Built")
+            .capacity(3)
+            .stack_from_str("AB")
+            .stack_from_str("BA")
+            .stack(Stack::new(3, 0, Vec::new()))
+            .build()
+            .unwrap();
+        assert_eq!(game.get_stage_name(), "Built");
+        assert_eq!(game.get_stacks().len(), 3);
+        assert!(game
+            .get_stacks()
+            .iter()
+            .all(|stack| stack.get_capacity() == 3));
+        assert!(game.hint().is_some());
+    }
+
+    #[test]
+    fn test_build_rejects_inconsistent_kind_counts() {
+        let result: Result<Game, BuildError> = Game::builder()
+            .stack_from_str("AB")
+            .stack_from_str("BA")
+            .stack_from_str("A.")
+            .build();
+        assert_eq!(
+            result.err(),
+            Some(BuildError::Unsortable {
+                kind: 'A',
+                count: 3
+            })
+        ); // Three A units, in stacks that only hold two.
+        assert_eq!(
+            Game::builder()
+                .capacity(2)
+                .stack_from_str("ABA")
+                .build()
+                .err(),
+            Some(BuildError::Overfilled {
+                stack: 0,
+                units: 3,
+                capacity: 2
+            })
+        );
+        assert_eq!(Game::builder().build().err(), Some(BuildError::NoStacks));
+    }
+
+    #[test]
+    fn test_build_can_reject_unsolvable_stages() {
+        let stuck: GameBuilder = Game::builder().stack_from_str("AB").stack_from_str("BA");
+        assert!(stuck.clone().build().is_ok()); // Only checked on request.
+        assert_eq!(
+            stuck.require_solvable().build().err(),
+            Some(BuildError::Unsolvable)
+        );
+        assert!(Game::builder()
+            .stack_from_str("AB.")
+            .stack_from_str("BA.")
+            .stack_from_str("...")
+            .require_solvable()
+            .build()
+            .is_ok());
+    }
+}
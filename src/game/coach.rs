@@ -0,0 +1,82 @@
+use crate::game::Game;
+
+// Coaching re-solves the board after every move, so it's only done while switched on. The
+// distance is the length of an optimal solution from the current board.
+impl Game {
+    pub fn set_coaching(&mut self, coaching: bool) {
+        self.coaching = coaching;
+        self.optimal_distance = None;
+    }
+
+    pub fn is_coaching(&self) -> bool {
+        self.coaching
+    }
+
+    // Makes the move, returning how many steps it added to the best possible finish. An
+    // optimal move brings the finish a step closer, anything else costs at least one more.
+    pub fn coached_move(&mut self, from: usize, to: usize) -> Option<usize> {
+        if !self.coaching {
+            self.move_legally(from, to);
+            return None;
+        }
+        let before: Option<usize> = self.optimal_distance.or_else(|| self.moves_remaining());
+        let turn: usize = self.turn;
+        self.move_legally(from, to);
+        if self.turn == turn {
+            self.optimal_distance = before; // The move was rejected, nothing changed.
+            return None;
+        }
+        let after: Option<usize> = self.moves_remaining();
+        self.optimal_distance = after;
+        match (before, after) {
+            (Some(before), Some(after)) if after + 1 > before => Some(after + 1 - before),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drifting_off_the_optimal_path_is_noticed() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![
+                vec![1, 2, 3, 1],
+                vec![3, 2, 1, 2],
+                vec![3, 0, 0, 0],
+                vec![0, 0, 0, 0],
+            ],
+            None,
+        );
+        game.set_coaching(true);
+        let best: usize = game.moves_remaining().unwrap();
+        let solution: Vec<(usize, usize)> = game.solve().unwrap();
+        let (detour, detour_distance): ((usize, usize), usize) = game
+            .legal_moves()
+            .into_iter()
+            .filter_map(|(from, to)| {
+                let distance: usize = game.simulate_move(from, to)?.moves_remaining()?;
+                Some(((from, to), distance))
+            })
+            .find(|(_, distance)| *distance >= best)
+            .expect("some move wastes a step");
+
+        let mut on_track: Game = game.clone();
+        on_track.set_coaching(true);
+        assert_eq!(on_track.coached_move(solution[0].0, solution[0].1), None);
+
+        assert_eq!(
+            game.coached_move(detour.0, detour.1),
+            Some(detour_distance + 1 - best)
+        );
+        assert_eq!(game.coached_move(0, 0), None); // Rejected moves aren't judged.
+
+        assert!(game.optimal_distance.is_some());
+        game.undo_move(); // The board changed, so the distance is solved again.
+        assert_eq!(game.optimal_distance, None);
+        game.set_coaching(false);
+        assert_eq!(game.coached_move(detour.0, detour.1), None);
+    }
+}
@@ -0,0 +1,45 @@
+use std::env;
+#[cfg(feature = "serde")]
+use std::fs;
+#[cfg(feature = "serde")]
+use std::io::{self, Write};
+#[cfg(feature = "serde")]
+use std::path::Path;
+use std::path::PathBuf;
+
+const DATA_DIR_NAME: &str = ".sorting-game";
+const STAGES_DIR_NAME: &str = "stages";
+const PALETTE_FILE_NAME: &str = "palette.conf";
+const KEYS_FILE_NAME: &str = "keys.conf";
+
+pub fn data_dir() -> Option<PathBuf> {
+    let home: String = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+    Some(PathBuf::from(home).join(DATA_DIR_NAME))
+}
+
+pub fn stages_dir() -> Option<PathBuf> {
+    Some(data_dir()?.join(STAGES_DIR_NAME))
+}
+
+pub fn palette_path() -> Option<PathBuf> {
+    Some(data_dir()?.join(PALETTE_FILE_NAME))
+}
+
+pub fn keys_path() -> Option<PathBuf> {
+    Some(data_dir()?.join(KEYS_FILE_NAME))
+}
+
+#[cfg(feature = "serde")]
+pub fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // Write next to the target and rename over it, so an existing file is never half-written.
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path: PathBuf = path.with_file_name(temp_name);
+    let mut file: fs::File = fs::File::create(&temp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    fs::rename(&temp_path, path)
+}
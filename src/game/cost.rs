@@ -0,0 +1,69 @@
+use crate::game::entry::Entry;
+use crate::game::Game;
+
+// The effort of a pour, on top of it counting as one move: a fixed part plus a part for every
+// unit carried, so big pours weigh more.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CostModel {
+    pub per_pour: usize,
+    pub per_unit: usize,
+}
+
+impl Default for CostModel {
+    fn default() -> CostModel {
+        CostModel {
+            per_pour: 1,
+            per_unit: 1,
+        }
+    }
+}
+
+impl CostModel {
+    pub fn cost_of(&self, entry: &Entry) -> usize {
+        self.per_pour + self.per_unit * entry.quantity()
+    }
+}
+
+impl Game {
+    pub fn total_cost(&self) -> usize {
+        self.ledger
+            .iter()
+            .map(|entry| self.cost_model.cost_of(entry))
+            .sum()
+    }
+
+    pub fn set_cost_model(&mut self, cost_model: CostModel) {
+        self.cost_model = cost_model;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_total_cost_of_the_ledger() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 2, 0], vec![2, 1, 1, 0], vec![0, 0, 0, 0]],
+            None,
+        );
+        assert_eq!(game.total_cost(), 0);
+        game.move_legally(0, 2); // Two units.
+        game.move_legally(1, 0); // Two units.
+        game.move_legally(1, 2); // One unit.
+        assert_eq!(
+            game.get_ledger()
+                .iter()
+                .map(|entry| entry.quantity())
+                .collect::<Vec<usize>>(),
+            vec![2, 2, 1]
+        );
+        assert_eq!(game.total_cost(), 3 + 5);
+
+        game.set_cost_model(CostModel {
+            per_pour: 2,
+            per_unit: 3,
+        });
+        assert_eq!(game.total_cost(), 3 * 2 + 5 * 3);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::game::game_config::GameConfig;
+use crate::game::Game;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DAILY_KINDS: usize = 5;
+const DAILY_CAPACITY: usize = 4;
+const DAILY_EMPTY_STACKS: usize = 2;
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Date {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    pub fn new(year: i64, month: u32, day: u32) -> Date {
+        Date { year, month, day }
+    }
+
+    pub fn today_utc() -> Date {
+        let seconds: u64 = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or(0);
+        Date::from_days_since_epoch((seconds / SECONDS_PER_DAY) as i64)
+    }
+
+    // Civil calendar conversion from days since 1970-01-01 (Howard Hinnant's algorithm).
+    pub fn from_days_since_epoch(days: i64) -> Date {
+        let shifted: i64 = days + 719_468;
+        let era: i64 = shifted.div_euclid(146_097);
+        let day_of_era: i64 = shifted.rem_euclid(146_097);
+        let year_of_era: i64 =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year: i64 =
+            day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index: i64 = (5 * day_of_year + 2) / 153;
+        let day: u32 = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+        let month: u32 = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        } as u32;
+        let year: i64 = year_of_era + era * 400 + i64::from(month <= 2);
+        Date { year, month, day }
+    }
+
+    // The inverse of `from_days_since_epoch`.
+    pub fn days_since_epoch(&self) -> i64 {
+        let year: i64 = self.year - i64::from(self.month <= 2);
+        let era: i64 = year.div_euclid(400);
+        let year_of_era: i64 = year.rem_euclid(400);
+        let month_index: i64 = match self.month {
+            month if month > 2 => i64::from(month) - 3,
+            month => i64::from(month) + 9,
+        };
+        let day_of_year: i64 = (153 * month_index + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era: i64 = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    pub fn previous(&self) -> Date {
+        Date::from_days_since_epoch(self.days_since_epoch() - 1)
+    }
+
+    fn seed(&self) -> u64 {
+        (self.year as u64) * 10_000 + u64::from(self.month) * 100 + u64::from(self.day)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Game {
+    pub fn daily(date: Date) -> Game {
+        let config: GameConfig = GameConfig::new(DAILY_CAPACITY, DAILY_KINDS, DAILY_EMPTY_STACKS)
+            .expect("the daily layout is valid");
+        let mut game: Game = Game::generate(&config, date.seed());
+        game.stage_name = format!("Daily - {}", date);
+        game.daily = Some(date);
+        game
+    }
+
+    // Kept with a saved game, so a resumed daily is still scored as that day's.
+    pub fn get_daily(&self) -> Option<Date> {
+        self.daily
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_daily_stage_depends_only_on_date() {
+        let date: Date = Date::new(2026, 10, 14);
+        let first: Game = Game::daily(date);
+        let second: Game = Game::daily(date);
+        let next_day: Game = Game::daily(Date::new(2026, 10, 15));
+
+        assert!(first.stacks == second.stacks);
+        assert_eq!(first.stage_name, "Daily - 2026-10-14");
+        assert!(first.stacks != next_day.stacks);
+        assert_eq!(first.get_daily(), Some(date));
+    }
+
+    #[test]
+    fn test_date_from_days() {
+        assert_eq!(Date::from_days_since_epoch(0), Date::new(1970, 1, 1));
+        assert_eq!(Date::from_days_since_epoch(59), Date::new(1970, 3, 1));
+        assert_eq!(Date::from_days_since_epoch(11_016), Date::new(2000, 2, 29));
+        assert_eq!(Date::from_days_since_epoch(20_740), Date::new(2026, 10, 14));
+        for days in [-1, 0, 59, 11_016, 20_740] {
+            assert_eq!(Date::from_days_since_epoch(days).days_since_epoch(), days);
+        }
+        assert_eq!(Date::new(2026, 3, 1).previous(), Date::new(2026, 2, 28));
+    }
+}
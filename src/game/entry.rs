@@ -1,9 +1,23 @@
 use crate::game::stack::kind::Kind;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Entry {
     pub from: usize,
     pub to: usize,
-    pub _kind: Kind,
-    pub quantity: usize,
+    #[cfg_attr(feature = "serde", serde(alias = "_kind"))] // Older saves used the private name.
+    pub kind: Kind,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub source_len: usize, // Units on the source before the move, 0 in older saves.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub player: usize, // Who made the move, always player 0 outside two-player games.
+}
+
+impl Entry {
+    // How many units the move poured, as recorded in its kind.
+    pub fn quantity(&self) -> usize {
+        self.kind.get_quantity()
+    }
 }
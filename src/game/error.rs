@@ -0,0 +1,335 @@
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MoveError {
+    OutOfBounds,
+    SameStack,
+    EmptySource,
+    TopMismatch,
+    NoRoom,
+    Locked,
+    NoProgress,
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason: &str = match self {
+            MoveError::OutOfBounds => "There is no such stack",
+            MoveError::SameStack => "Units must be moved to a different stack",
+            MoveError::EmptySource => "There are no units to move in that stack",
+            MoveError::TopMismatch => {
+                "Units can only be moved towards identical units, or empty stacks"
+            }
+            MoveError::NoRoom => "The target stack is full",
+            MoveError::Locked => "That stack is locked until its kind is sorted",
+            MoveError::NoProgress => "That only moves the stack around, it gets nothing done",
+        };
+        write!(f, "{}", reason)
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ReplayError {
+    pub index: usize,
+    pub error: MoveError,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "move {} can't be replayed: {}",
+            self.index + 1,
+            self.error
+        )
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ScriptError {
+    pub index: usize,
+    pub accepted: usize, // Applied before the broken move, and left on the board.
+    pub error: MoveError,
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "move {} of the script is broken: {} ({} applied before it)",
+            self.index + 1,
+            self.error,
+            self.accepted
+        )
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConfigError {
+    ZeroCapacity,
+    NoKinds,
+    Overfilled {
+        stack: usize,
+        units: usize,
+        capacity: usize,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::ZeroCapacity => write!(f, "stacks must hold at least one unit"),
+            ConfigError::NoKinds => write!(f, "a stage needs at least one kind of unit"),
+            ConfigError::Overfilled {
+                stack,
+                units,
+                capacity,
+            } => write!(
+                f,
+                "stack {} holds {} units but only has room for {}",
+                stack + 1,
+                units,
+                capacity
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StackParseError {
+    pub character: char,
+    pub position: usize,
+}
+
+impl fmt::Display for StackParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown unit '{}' at position {}, expected a letter or '.'",
+            self.character,
+            self.position + 1
+        )
+    }
+}
+
+impl std::error::Error for StackParseError {}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum StageParseError {
+    Metadata { line: usize, text: String },
+    Stack { line: usize, error: StackParseError },
+    NoStacks,
+    Unsortable { kind: char, count: usize },
+    Stuck, // Nothing can be moved from the start, often because of a lock.
+}
+
+impl fmt::Display for StageParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StageParseError::Metadata { line, text } => {
+                write!(f, "line {}: expected 'key: value', found '{}'", line, text)
+            }
+            StageParseError::Stack { line, error } => write!(f, "line {}: {}", line, error),
+            StageParseError::NoStacks => write!(f, "the stage has no stacks"),
+            StageParseError::Unsortable { kind, count } => write!(
+                f,
+                "the {} units of kind '{}' don't fit together in any stack",
+                count, kind
+            ),
+            StageParseError::Stuck => write!(f, "no move can be made on the starting board"),
+        }
+    }
+}
+
+impl std::error::Error for StageParseError {}
+
+#[derive(Debug)]
+pub enum StageError {
+    Io(std::io::Error),
+    Read {
+        file: PathBuf,
+        error: std::io::Error,
+    },
+    Parse {
+        file: PathBuf,
+        error: StageParseError,
+    },
+}
+
+impl fmt::Display for StageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StageError::Io(error) => write!(f, "could not read the stages: {}", error),
+            StageError::Read { file, error } => write!(f, "{}: {}", file.display(), error),
+            StageError::Parse { file, error } => write!(f, "{}: {}", file.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for StageError {}
+
+impl From<std::io::Error> for StageError {
+    fn from(error: std::io::Error) -> StageError {
+        StageError::Io(error)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub enum LoadError {
+    Io(std::io::Error),
+    Parse(String),
+    Inconsistent(String),
+}
+
+#[cfg(feature = "serde")]
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(error) => write!(f, "could not read the save file: {}", error),
+            LoadError::Parse(reason) => write!(f, "the save file is corrupt: {}", reason),
+            LoadError::Inconsistent(reason) => {
+                write!(f, "the saved game is inconsistent: {}", reason)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::error::Error for LoadError {}
+
+#[cfg(feature = "serde")]
+impl From<std::io::Error> for LoadError {
+    fn from(error: std::io::Error) -> LoadError {
+        LoadError::Io(error)
+    }
+}
+
+#[derive(Debug)]
+pub enum KeyBindingError {
+    Io(std::io::Error),
+    Syntax {
+        line: usize,
+        text: String,
+    },
+    UnknownAction {
+        line: usize,
+        action: String,
+    },
+    InvalidKey {
+        line: usize,
+        key: String,
+    },
+    Conflict {
+        key: char,
+        first: String,
+        second: String,
+    },
+}
+
+impl fmt::Display for KeyBindingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyBindingError::Io(error) => write!(f, "could not read the key bindings: {}", error),
+            KeyBindingError::Syntax { line, text } => {
+                write!(
+                    f,
+                    "line {}: expected '<action> = <key>', got '{}'",
+                    line, text
+                )
+            }
+            KeyBindingError::UnknownAction { line, action } => {
+                write!(f, "line {}: there is no '{}' action", line, action)
+            }
+            KeyBindingError::InvalidKey { line, key } => write!(
+                f,
+                "line {}: '{}' can't be a key, use a single character that isn't a digit",
+                line, key
+            ),
+            KeyBindingError::Conflict { key, first, second } => {
+                write!(f, "'{}' is bound to both {} and {}", key, first, second)
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeyBindingError {}
+
+impl From<std::io::Error> for KeyBindingError {
+    fn from(error: std::io::Error) -> KeyBindingError {
+        KeyBindingError::Io(error)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GameError {
+    NoStages,
+    InputClosed, // The input ran out before the player quit.
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::NoStages => write!(f, "there are no stages to play"),
+            GameError::InputClosed => write!(f, "the input was closed before the game was quit"),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum BuildError {
+    NoStacks,
+    Stack {
+        stack: usize,
+        error: StackParseError,
+    },
+    Overfilled {
+        stack: usize,
+        units: usize,
+        capacity: usize,
+    },
+    Unsortable {
+        kind: char,
+        count: usize,
+    },
+    Unsolvable,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::NoStacks => write!(f, "the stage has no stacks"),
+            BuildError::Stack { stack, error } => write!(f, "stack {}: {}", stack + 1, error),
+            BuildError::Overfilled {
+                stack,
+                units,
+                capacity,
+            } => write!(
+                f,
+                "stack {} holds {} units but only has room for {}",
+                stack + 1,
+                units,
+                capacity
+            ),
+            BuildError::Unsortable { kind, count } => write!(
+                f,
+                "the {} units of kind '{}' don't fit together in any stack",
+                count, kind
+            ),
+            BuildError::Unsolvable => write!(f, "no sequence of moves sorts the stage"),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
@@ -0,0 +1,88 @@
+use crate::game::solver::StateKey;
+use crate::game::Game;
+use std::collections::HashSet;
+
+// Fast-forwarding plays the moves that leave the player nothing to decide, and stops at the
+// first board with a real choice on it.
+impl Game {
+    pub fn set_fast_forward(&mut self, fast_forward: bool) {
+        self.fast_forward = fast_forward;
+    }
+
+    // The only move besides taking back the last one, or else the only move that sorts a kind.
+    // Moves that lead to the same board up to the order of the stacks count as one.
+    pub fn obvious_move(&self) -> Option<(usize, usize)> {
+        let last: Option<(usize, usize)> = self.ledger.last().map(|entry| (entry.from, entry.to));
+        let mut boards: HashSet<StateKey> = HashSet::new();
+        let mut moves: Vec<((usize, usize), Game)> = Vec::new();
+        for (from, to) in self.legal_moves() {
+            if last == Some((to, from)) {
+                continue;
+            }
+            if let Some(next) = self.simulate_move(from, to) {
+                if boards.insert(next.canonical_key()) {
+                    moves.push(((from, to), next));
+                }
+            }
+        }
+        if let [(only, _)] = moves.as_slice() {
+            return Some(*only);
+        }
+        let sorted: usize = self.completed_kinds().len();
+        let sorting: Vec<(usize, usize)> = moves
+            .into_iter()
+            .filter(|(_, next)| next.completed_kinds().len() > sorted)
+            .map(|(only, _)| only)
+            .collect();
+        match sorting.as_slice() {
+            [only] => Some(*only),
+            _ => None,
+        }
+    }
+
+    // Plays obvious moves until there are none, returning how many were made. A board seen
+    // before ends it too, so two stacks can't pass the same units back and forth forever.
+    pub fn fast_forward(&mut self, mut on_frame: impl FnMut(&Game)) -> usize {
+        let mut seen: HashSet<StateKey> = HashSet::from([self.canonical_key()]);
+        let mut made: usize = 0;
+        while self.outcome().is_none() {
+            let Some((from, to)) = self.obvious_move() else {
+                break;
+            };
+            if self.try_move(from, to).is_err() {
+                break;
+            }
+            made += 1;
+            on_frame(self);
+            if !seen.insert(self.canonical_key()) {
+                break;
+            }
+        }
+        made
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forced_moves_play_themselves() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 1, 2], vec![2, 2, 0], vec![1, 0, 0]], None);
+        assert_eq!(game.obvious_move(), Some((0, 1)));
+        let mut frames: usize = 0;
+        assert_eq!(game.fast_forward(|_| frames += 1), 2);
+        assert_eq!(frames, 2);
+        assert!(game.stage_complete());
+    }
+
+    #[test]
+    fn test_branching_board_is_left_to_the_player() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        assert_eq!(game.obvious_move(), None);
+        assert_eq!(game.fast_forward(|_| {}), 0);
+        assert_eq!(game.get_move_count(), 0);
+    }
+}
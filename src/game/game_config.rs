@@ -0,0 +1,112 @@
+use crate::game::error::ConfigError;
+use crate::game::stack::Stack;
+use crate::game::Game;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct GameConfig {
+    capacity: usize,
+    kinds: usize,
+    empty_stacks: usize,
+}
+
+impl GameConfig {
+    pub fn new(
+        capacity: usize,
+        kinds: usize,
+        empty_stacks: usize,
+    ) -> Result<GameConfig, ConfigError> {
+        if capacity == 0 {
+            return Err(ConfigError::ZeroCapacity);
+        }
+        if kinds == 0 {
+            return Err(ConfigError::NoKinds);
+        }
+        Ok(GameConfig {
+            capacity,
+            kinds,
+            empty_stacks,
+        })
+    }
+
+    pub fn get_capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn get_kinds(&self) -> usize {
+        self.kinds
+    }
+
+    pub fn get_empty_stacks(&self) -> usize {
+        self.empty_stacks
+    }
+
+    // Every listed stack gets the configured capacity, followed by the configured empty stacks.
+    pub fn build_stacks(&self, vecs: Vec<Vec<usize>>) -> Result<Vec<Stack>, ConfigError> {
+        let mut stacks: Vec<Stack> = Vec::new();
+        for (stack_ind, vec) in vecs.into_iter().enumerate() {
+            let units: usize = vec.len();
+            match Stack::new_with_capacity(self.capacity, vec) {
+                Some(stack) => stacks.push(stack),
+                None => {
+                    return Err(ConfigError::Overfilled {
+                        stack: stack_ind,
+                        units,
+                        capacity: self.capacity,
+                    })
+                }
+            }
+        }
+        for _ in 0..self.empty_stacks {
+            stacks.push(Stack::new(self.capacity, 0, Vec::new()));
+        }
+        Ok(stacks)
+    }
+}
+
+impl Game {
+    pub fn new_from_config(
+        config: &GameConfig,
+        vecs: Vec<Vec<usize>>,
+        stage_name: Option<String>,
+    ) -> Result<Game, ConfigError> {
+        Ok(Game::new(config.build_stacks(vecs)?, stage_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_validation() {
+        assert_eq!(GameConfig::new(0, 3, 1), Err(ConfigError::ZeroCapacity));
+        assert_eq!(GameConfig::new(4, 0, 1), Err(ConfigError::NoKinds));
+        assert!(GameConfig::new(1, 1, 0).is_ok());
+    }
+
+    #[test]
+    fn test_stage_from_config() {
+        let config: GameConfig = GameConfig::new(3, 2, 1).unwrap();
+        let game: Game =
+            Game::new_from_config(&config, vec![vec![1, 2], vec![2, 1, 2]], None).unwrap();
+        assert_eq!(game.stacks.len(), 3);
+        assert!(game.stacks.iter().all(|stack| stack.get_capacity() == 3));
+        assert_eq!(game.stacks[0].get_vacancy(), 1);
+        assert!(game.stacks[2].is_vacant());
+    }
+
+    #[test]
+    fn test_overfilled_stack_is_rejected() {
+        let config: GameConfig = GameConfig::new(2, 2, 1).unwrap();
+        let result: Result<Game, ConfigError> =
+            Game::new_from_config(&config, vec![vec![1, 2], vec![2, 1, 1]], None);
+        assert_eq!(
+            result.err(),
+            Some(ConfigError::Overfilled {
+                stack: 1,
+                units: 3,
+                capacity: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use crate::game::game_config::GameConfig;
+use crate::game::rng::Rng;
+use crate::game::stack::kind::{Kind, KindId};
+use crate::game::stack::Stack;
+use crate::game::timer::StageTimer;
+use crate::game::Game;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+const SHUFFLE_MOVES_PER_UNIT: usize = 3;
+
+impl Game {
+    pub fn generate(config: &GameConfig, seed: u64) -> Game {
+        let kinds: usize = config.get_kinds();
+        let capacity: usize = config.get_capacity();
+        let sorted: Vec<Vec<usize>> = (1..=kinds).map(|kind| vec![kind; capacity]).collect();
+        let mut stacks: Vec<Stack> = config
+            .build_stacks(sorted)
+            .expect("sorted stacks fill their capacity exactly");
+
+        let mut rng: Rng = Rng::new(seed);
+        Game::scramble(
+            &mut stacks,
+            &mut rng,
+            kinds * capacity * SHUFFLE_MOVES_PER_UNIT,
+        );
+        Game::new(stacks, Some(format!("Generated - {}", seed)))
+    }
+
+    // A new board from the same units, sorted into the same stacks and then scrambled like a
+    // generated stage, so it can always be solved. The moves, par and locks of the old board
+    // are dropped, and its clock starts over. A board whose kinds can't all be sorted is left
+    // as it is, and false is returned.
+    pub fn reshuffle(&mut self, seed: u64) -> bool {
+        let Some(mut stacks) = Game::sorted_layout(&self.stacks, &self.units_per_kind) else {
+            return false;
+        };
+        let mut rng: Rng = Rng::new(seed);
+        let steps: usize = self.total_units() * SHUFFLE_MOVES_PER_UNIT;
+        Game::scramble(&mut stacks, &mut rng, steps);
+        self.initial_stacks = stacks;
+        self.restart();
+        self.stage_name = format!("Reshuffled - {}", seed);
+        self.reshuffled = true;
+        self.par = None;
+        self.meta.par = None; // Set for the stage's own board.
+        self.undos_used = 0;
+        self.celebrated = false;
+        if self.face_down {
+            self.hide_units();
+        }
+        let running: bool = self.timer.is_running();
+        self.timer = StageTimer::default();
+        if running {
+            self.resume();
+        }
+        true
+    }
+
+    // Empty stacks of the same capacities and labels, with every kind gathered in one of them.
+    // Like `unsortable_kind`, the largest kinds go to the tallest stacks.
+    fn sorted_layout(
+        stacks: &[Stack],
+        units_per_kind: &HashMap<KindId, usize>,
+    ) -> Option<Vec<Stack>> {
+        let mut sorted: Vec<Stack> = stacks
+            .iter()
+            .map(|stack| {
+                let mut empty: Stack = Stack::with_capacity(stack.get_capacity());
+                if let Some(label) = stack.get_label() {
+                    empty.set_label(label);
+                }
+                empty
+            })
+            .collect();
+        let mut kinds: Vec<(usize, KindId)> = units_per_kind
+            .iter()
+            .map(|(kind, count)| (*count, *kind))
+            .collect();
+        kinds.sort_by(|a, b| b.cmp(a));
+        let mut tallest: Vec<usize> = (0..stacks.len()).collect();
+        tallest.sort_by_key(|ind| Reverse(stacks[*ind].get_capacity()));
+        if kinds.len() > tallest.len() {
+            return None;
+        }
+        for ((count, kind), ind) in kinds.into_iter().zip(tallest) {
+            sorted[ind].push_immigrants(Kind::new(kind, count)).ok()?;
+        }
+        Some(sorted)
+    }
+
+    // Starting from a sorted board, every step is the exact reverse of a legal pour,
+    // so replaying the steps backwards always solves the scrambled board.
+    fn scramble(stacks: &mut [Stack], rng: &mut Rng, steps: usize) {
+        for _ in 0..steps {
+            let candidates: Vec<(usize, usize, usize)> = Game::reverse_moves(stacks);
+            if candidates.is_empty() {
+                break;
+            }
+            let (from, to, quantity) = candidates[rng.below(candidates.len())];
+            let lifted = stacks[from].pop_residents_with_limit(Some(quantity));
+            stacks[to]
+                .push_immigrants(lifted)
+                .expect("reverse moves only target stacks with room");
+        }
+    }
+
+    // Lifting units off `from` onto `to` can be poured straight back when `from` is left with
+    // the same kind (or nothing) on top, and `to` doesn't already hold that kind on top,
+    // otherwise the pour back would carry extra units. Moving a whole stack onto an empty one
+    // is skipped, since pouring it back would be a pointless relocation.
+    fn reverse_moves(stacks: &[Stack]) -> Vec<(usize, usize, usize)> {
+        let mut moves: Vec<(usize, usize, usize)> = Vec::new();
+        for (from, source) in stacks.iter().enumerate() {
+            let run: usize = source.top_run_len();
+            for quantity in 1..=run {
+                let empties_source: bool = quantity == source.get_occupancy();
+                if quantity == run && !empties_source {
+                    continue;
+                }
+                for (to, target) in stacks.iter().enumerate() {
+                    let fits: bool = target.get_vacancy() >= quantity;
+                    let merges: bool = target.get_top_unit_id() == source.get_top_unit_id();
+                    let relocates: bool = empties_source && target.is_vacant();
+                    if to != from && fits && !merges && !relocates {
+                        moves.push((from, to, quantity));
+                    }
+                }
+            }
+        }
+        moves
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_stages_are_solvable() {
+        let config: GameConfig = GameConfig::new(3, 3, 1).unwrap();
+        let mut scrambled: usize = 0;
+        for seed in 0..8 {
+            let game: Game = Game::generate(&config, seed);
+            assert_eq!(game.stacks.len(), 4);
+            let solution: Option<Vec<(usize, usize)>> = game.solve();
+            assert!(solution.is_some(), "seed {} is unsolvable", seed);
+            scrambled += usize::from(!solution.unwrap().is_empty());
+        }
+        assert!(scrambled > 0);
+    }
+
+    #[test]
+    fn test_reshuffle_keeps_the_units_and_stays_solvable() {
+        let config: GameConfig = GameConfig::new(3, 3, 1).unwrap();
+        let mut game: Game = Game::generate(&config, 5);
+        game.run_script(&game.solve().unwrap()[..1]).unwrap();
+        let units: HashMap<KindId, usize> = Game::count_kinds(&game.stacks);
+        let capacities: Vec<usize> = game
+            .stacks
+            .iter()
+            .map(|stack| stack.get_capacity())
+            .collect();
+        for seed in 0..4 {
+            let mut reshuffled: Game = game.clone();
+            assert!(reshuffled.reshuffle(seed));
+            assert_eq!(Game::count_kinds(&reshuffled.stacks), units);
+            let reshuffled_capacities: Vec<usize> = reshuffled
+                .stacks
+                .iter()
+                .map(|stack| stack.get_capacity())
+                .collect();
+            assert_eq!(reshuffled_capacities, capacities);
+            assert!(reshuffled.solve().is_some(), "seed {} is unsolvable", seed);
+            assert_eq!(reshuffled.get_move_count(), 0);
+            assert!(reshuffled.initial_layout() == reshuffled.stacks.as_slice());
+        }
+        let (mut first, mut second): (Game, Game) = (game.clone(), game.clone());
+        first.reshuffle(9);
+        second.reshuffle(9);
+        assert!(first.stacks == second.stacks);
+    }
+
+    #[test]
+    fn test_reshuffle_keeps_face_down_units_and_refuses_unsortable_boards() {
+        let config: GameConfig = GameConfig::new(3, 3, 1).unwrap();
+        let mut game: Game = Game::generate(&config, 5);
+        game.hide_units();
+        assert!(game.reshuffle(3));
+        assert!(game.has_face_down_units());
+        assert!(game.initial_layout() == game.stacks.as_slice());
+
+        // Three units of one kind, but no stack holds more than two.
+        let mut unsortable: Game = Game::new_from_vecs(vec![vec![1, 1], vec![1, 0]], None);
+        let before: Game = unsortable.clone();
+        assert!(!unsortable.reshuffle(3));
+        assert_eq!(unsortable, before);
+        assert!(!unsortable.reshuffled);
+    }
+
+    #[test]
+    fn test_same_seed_same_stage() {
+        let config: GameConfig = GameConfig::new(4, 4, 2).unwrap();
+        let first: Game = Game::generate(&config, 42);
+        let second: Game = Game::generate(&config, 42);
+        let other: Game = Game::generate(&config, 43);
+        assert!(first.stacks == second.stacks);
+        assert!(first.stacks != other.stacks);
+    }
+}
@@ -1,11 +1,58 @@
+use crate::game::achievements::Achievement;
+use crate::game::daily::Date;
+use crate::game::input;
+use crate::game::keys::KeyBindings;
+use crate::game::layout;
+use crate::game::scores::{BestScore, Leaderboard};
+use crate::game::solver::AutoSolveOutcome;
+use crate::game::stack::kind::{Kind, KindId};
+use crate::game::style::{EmptyStackLook, RenderStyle};
 use crate::game::Game;
+use std::fmt::Write as _;
 use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
 
+const AUTO_SOLVE_FRAME: Duration = Duration::from_millis(400);
+const UNDO_ALL_FRAME: Duration = Duration::from_millis(150);
+const FAST_FORWARD_FRAME: Duration = Duration::from_millis(250);
+const WIN_FRAME: Duration = Duration::from_millis(120);
+const WIN_FRAMES: usize = 9;
+const SPARKLES: [&str; 3] = ["✦", "✧", "·"];
+const HISTORY_LENGTH: usize = 8;
+const HISTORY_WIDTH: usize = 16;
+const ESCAPE: &str = "\x1b";
+const FACE_DOWN: char = '?';
+const DEV_SKIP: &str = "skip";
+const RESIZE_POLL: Duration = Duration::from_millis(200);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum MenuOption {
     Help,
     Move,
     Reset,
     Undo,
+    UndoAll,
+    Redo,
+    Jump,
+    Save,
+    Hint,
+    AutoSolve,
+    History,
+    Pause,
+    MovesLeft,
+    Coach,
+    Reshuffle,
+    Skip, // Developer only, never bound to a key.
+    Quit,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum StageChoice {
+    Tutorial,
+    Stage(usize),
+    All,
+    Daily,
     Quit,
 }
 
@@ -17,91 +64,626 @@ pub struct UserInput {
 impl UserInput {
     pub fn new_menu_option(menu_option: MenuOption) -> UserInput {
         UserInput {
-            menu_option: menu_option,
+            menu_option,
             stack_move: None,
         }
     }
+
+    pub fn new_move(from: usize, to: usize) -> UserInput {
+        UserInput {
+            menu_option: MenuOption::Move,
+            stack_move: Some((from, to)),
+        }
+    }
+}
+
+// Where the turn loop gets its next action from, so it can be driven without a terminal.
+pub trait InputSource {
+    fn next_input(&mut self, game: &mut Game) -> UserInput;
+}
+
+pub struct TerminalInput;
+
+impl InputSource for TerminalInput {
+    fn next_input(&mut self, game: &mut Game) -> UserInput {
+        game.read_valid_input()
+    }
+}
+
+// A scripted source hands out its inputs in order, then quits.
+impl InputSource for Vec<UserInput> {
+    fn next_input(&mut self, _game: &mut Game) -> UserInput {
+        match self.is_empty() {
+            true => UserInput::new_menu_option(MenuOption::Quit),
+            false => self.remove(0),
+        }
+    }
 }
 
 impl Game {
     pub fn render(&self) {
-        // Clear the screen and move the cursor to the top-left corner
-        print!("\x1B[2J\x1B[H");
-        io::stdout().flush().unwrap(); // Ensure the screen is cleared immediately
-        println!("|**************|\n| Sorting Game |\n****************");
-        println!("{}", self.stage_name); // Display the current game name
-        println!("Turn - {}", self.turn);
-        println!();
-
-        for (stack_ind, stack) in self.stacks.iter().enumerate() {
-            let mut buffer: String = "".to_string();
-            for unit_id in stack.iter_unit_ids() {
-                let unit_index: usize = self.get_kind_index(unit_id);
-                let color: [u8; 3] = COLORS[unit_index % COLORS.len()].clone();
-                buffer.push_str(
-                    format!(
-                        "\x1b[38;2;{};{};{}m{:>2}\x1b[0m ",
-                        color[0], color[1], color[2], unit_id,
-                    )
-                    .as_str(),
-                );
+        Game::clear_screen(self.style);
+        print!("{}", self.render_to_string(self.style));
+        io::stdout().flush().unwrap(); // Ensure the frame is displayed immediately
+    }
+
+    // Sized for the terminal, and plain when NO_COLOR asks for it. The explicit width takes the
+    // style as given.
+    pub fn render_to_string(&self, style: RenderStyle) -> String {
+        self.render_to_string_with_width(style.honoring_no_color(), layout::terminal_width())
+    }
+
+    pub fn render_to_string_with_width(&self, style: RenderStyle, width: usize) -> String {
+        let mut frame: String = String::new();
+        let _ = writeln!(frame, "{}", style.title());
+        let _ = writeln!(frame, "{}", self.stage_name); // Display the current game name
+        if let Some((number, total)) = self.progress {
+            let bar: String = style.progress_bar(number - 1, total);
+            let _ = writeln!(frame, "Stage {} / {} {}", number, total, bar);
+        }
+        let par: String = match self.get_par() {
+            Some(par) => par.to_string(),
+            None => style.dash().to_string(),
+        };
+        // The clock only shows once the stage is being played, and moves on with every frame.
+        let time: String = match self.timer.has_started() {
+            true => format!(" | time {}", Game::format_elapsed(self.get_elapsed())),
+            false => "".to_string(),
+        };
+        let undos: String = match self.undos_left() {
+            Some(left) => format!(" | undos left: {}", left),
+            None => "".to_string(),
+        };
+        let moves: String = match self.move_limit {
+            Some(limit) => format!("moves: {} / {} | par {}", self.get_move_count(), limit, par),
+            None => format!("moves: {} / par {}", self.get_move_count(), par),
+        };
+        let player: String = match self.two_player {
+            true => format!("Player {} to move | ", self.current_player + 1),
+            false => "".to_string(),
+        };
+        let _ = writeln!(
+            frame,
+            "Turn - {} | {}{}{}{}",
+            self.turn, player, moves, undos, time
+        );
+        let _ = writeln!(frame, "{}\n", self.kinds_legend(style));
+
+        let completed: Vec<usize> = self.shown_completed_stacks();
+        let cells: Vec<String> = (0..self.stacks.len())
+            .map(|stack_ind| {
+                let is_complete: bool = completed.contains(&stack_ind);
+                match style {
+                    RenderStyle::Color => self.format_stack(stack_ind, is_complete),
+                    RenderStyle::Ascii => self.format_stack_ascii(stack_ind, is_complete),
+                }
+            })
+            .collect();
+        match self.show_history {
+            true => {
+                let board_width: usize = width.saturating_sub(HISTORY_WIDTH + layout::COLUMN_GAP);
+                let board: String = layout::grid(&cells, board_width);
+                frame.push_str(&layout::side_by_side(&board, &self.history_lines(style)));
             }
-            for _ in 0..stack.get_vacancy() {
-                buffer.push_str("__ ");
+            false => frame.push_str(&layout::grid(&cells, width)),
+        }
+        frame.push('\n');
+        frame
+    }
+
+    // Face-down units mustn't be given away by a done marker, so a stack is only marked once
+    // every unit on it shows, and a kind once its whole run does.
+    fn shown_completed_stacks(&self) -> Vec<usize> {
+        self.completed_stacks()
+            .into_iter()
+            .filter(|stack_ind| self.stacks[*stack_ind].get_hidden() == 0)
+            .collect()
+    }
+
+    fn shown_completed_kinds(&self) -> Vec<Kind> {
+        self.completed_kinds()
+            .into_iter()
+            .filter(|kind| self.shows_sorted(kind.get_id()))
+            .collect()
+    }
+
+    // Each kind marked off once it's gathered, so the player can see what's left to do.
+    fn kinds_legend(&self, style: RenderStyle) -> String {
+        let completed: Vec<Kind> = self.shown_completed_kinds();
+        let labels: Vec<String> = self
+            .sorted_kind_ids()
+            .into_iter()
+            .map(|id| {
+                let index: usize = self.get_kind_index(id);
+                let label: String = match style {
+                    RenderStyle::Color => {
+                        let color: [u8; 3] = self.palette.color(index);
+                        let text: String = match self.palette.symbol(index) {
+                            Some(symbol) => symbol.to_string(),
+                            None => id.to_string(),
+                        };
+                        format!(
+                            "\x1b[38;2;{};{};{}m{}\x1b[0m",
+                            color[0], color[1], color[2], text
+                        )
+                    }
+                    RenderStyle::Ascii => Kind::char_from_id(id).unwrap_or('?').to_string(),
+                };
+                match completed.iter().any(|kind| kind.get_id() == id) {
+                    true => format!("{}{}", label, style.complete_marker()),
+                    false => label,
+                }
+            })
+            .collect();
+        format!("Kinds: {}", labels.join("  "))
+    }
+
+    // The most recent moves first, as "from→to ×quantity (kind)".
+    fn history_lines(&self, style: RenderStyle) -> Vec<String> {
+        let mut lines: Vec<String> = vec!["History:".to_string()];
+        for entry in self.ledger.iter().rev().take(HISTORY_LENGTH) {
+            let kind: String = match style {
+                RenderStyle::Color => entry.kind.get_id().to_string(),
+                RenderStyle::Ascii => Kind::char_from_id(entry.kind.get_id())
+                    .unwrap_or('?')
+                    .to_string(),
+            };
+            lines.push(format!(
+                "{}{}{} {}{} ({})",
+                entry.from + 1,
+                style.arrow(),
+                entry.to + 1,
+                style.times(),
+                entry.quantity(),
+                kind
+            ));
+        }
+        if self.ledger.is_empty() {
+            lines.push("no moves yet".to_string());
+        }
+        lines
+    }
+
+    fn format_stack(&self, stack_ind: usize, is_complete: bool) -> String {
+        let stack = &self.stacks[stack_ind];
+        let mut buffer: String = "".to_string();
+        for (unit_id, count) in self.cell_runs(stack_ind) {
+            let count: String = RenderStyle::Color.run_count(count);
+            let Some(unit_id) = unit_id else {
+                buffer.push_str(&format!("\x1b[90m{:>2}{}\x1b[0m ", FACE_DOWN, count));
+                continue;
+            };
+            let unit_index: usize = self.get_kind_index(unit_id);
+            let color: [u8; 3] = self.palette.color(unit_index);
+            let cell: String = match self.palette.symbol(unit_index) {
+                Some(symbol) => format!("{:>2}{}", symbol, count),
+                None => format!("{:>2}{}", unit_id, count),
+            };
+            buffer.push_str(
+                format!(
+                    "\x1b[38;2;{};{};{}m{}\x1b[0m ",
+                    color[0], color[1], color[2], cell,
+                )
+                .as_str(),
+            );
+        }
+        let slot: &str = match self.is_dotted(stack_ind) {
+            true => RenderStyle::Color.dotted_slot(),
+            false => "__",
+        };
+        for _ in 0..stack.get_vacancy() {
+            buffer.push_str(slot);
+            buffer.push(' ');
+        }
+        // Finished stacks get a bold label and a checkmark.
+        let (label, done) = match is_complete {
+            true => (
+                format!("\x1b[1m{:>2}\x1b[0m", stack_ind + 1),
+                RenderStyle::Color.complete_marker(),
+            ),
+            false => (format!("{:>2}", stack_ind + 1), ""),
+        };
+        format!(
+            "{}: {}{}{}{}{}{}",
+            label,
+            buffer,
+            done,
+            self.empty_marker(stack_ind),
+            self.lock_marker(stack_ind, RenderStyle::Color),
+            self.label_marker(stack_ind, RenderStyle::Color),
+            self.highlight_marker(stack_ind)
+        )
+    }
+
+    fn format_stack_ascii(&self, stack_ind: usize, is_complete: bool) -> String {
+        let stack = &self.stacks[stack_ind];
+        let mut slots: Vec<String> = self
+            .cell_runs(stack_ind)
+            .into_iter()
+            .map(|(unit_id, count)| {
+                let unit: char = match unit_id {
+                    Some(unit_id) => Kind::char_from_id(unit_id).unwrap_or('?'),
+                    None => FACE_DOWN,
+                };
+                format!("{}{}", unit, RenderStyle::Ascii.run_count(count))
+            })
+            .collect();
+        let slot: &str = match self.is_dotted(stack_ind) {
+            true => RenderStyle::Ascii.dotted_slot(),
+            false => ".",
+        };
+        slots.extend(std::iter::repeat_n(slot.to_string(), stack.get_vacancy()));
+        let done: &str = match is_complete {
+            true => RenderStyle::Ascii.complete_marker(),
+            false => "",
+        };
+        format!(
+            "{:>2}: |{}|{}{}{}{}{}",
+            stack_ind + 1,
+            slots.join(" "),
+            done,
+            self.empty_marker(stack_ind),
+            self.lock_marker(stack_ind, RenderStyle::Ascii),
+            self.label_marker(stack_ind, RenderStyle::Ascii),
+            self.highlight_marker(stack_ind)
+        )
+    }
+
+    // The cells of a stack from the bottom, `None` where face down. In compact mode adjacent
+    // cells that look the same share one entry, otherwise every count is 1.
+    fn cell_runs(&self, stack_ind: usize) -> Vec<(Option<KindId>, usize)> {
+        let stack = &self.stacks[stack_ind];
+        let mut runs: Vec<(Option<KindId>, usize)> = Vec::new();
+        for (cell, unit_id) in stack.iter_unit_ids().enumerate() {
+            let shown: Option<KindId> = match stack.is_face_down(cell) {
+                true => None,
+                false => Some(unit_id),
+            };
+            match runs.last_mut() {
+                Some((last, count)) if self.compact_runs && *last == shown => *count += 1,
+                _ => runs.push((shown, 1)),
+            }
+        }
+        runs
+    }
+
+    fn is_dotted(&self, stack_ind: usize) -> bool {
+        self.empty_look == EmptyStackLook::Dotted && self.stacks[stack_ind].is_vacant()
+    }
+
+    fn empty_marker(&self, stack_ind: usize) -> &'static str {
+        match self.is_dotted(stack_ind) {
+            true => " empty",
+            false => "",
+        }
+    }
+
+    fn lock_marker(&self, stack_ind: usize, style: RenderStyle) -> String {
+        match self.stacks[stack_ind].get_lock() {
+            Some(kind) if self.is_locked(stack_ind) => format!(
+                "{} until {} is sorted",
+                style.lock_marker(),
+                Kind::char_from_id(kind).unwrap_or('?')
+            ),
+            _ => "".to_string(),
+        }
+    }
+
+    // The board lists the stacks one per row, so the label follows its stack's row.
+    fn label_marker(&self, stack_ind: usize, style: RenderStyle) -> String {
+        match self.stacks[stack_ind].get_label() {
+            Some(label) => format!(" {} {}", style.dash(), label),
+            None => "".to_string(),
+        }
+    }
+
+    fn highlight_marker(&self, stack_ind: usize) -> String {
+        if let Some(from) = self.selected {
+            if from == stack_ind {
+                return " <- selected".to_string();
             }
-            println!("{:>2}: {}", stack_ind + 1, buffer);
+            return match self
+                .destinations(from)
+                .iter()
+                .find(|(to, _)| *to == stack_ind)
+            {
+                Some((_, quantity)) => format!(" <- pours {}", quantity),
+                None => "".to_string(),
+            };
+        }
+        match self.highlight {
+            Some((from, _)) if from == stack_ind => " <- take from here".to_string(),
+            Some((_, to)) if to == stack_ind => " <- pour here".to_string(),
+            _ => "".to_string(),
         }
-        println!();
     }
 
-    pub fn stage_complete_prompt(&self, is_last_stage: bool) {
+    pub fn stage_complete_prompt(
+        &self,
+        is_last_stage: bool,
+        is_new_best: bool,
+        best: Option<&BestScore>,
+        unlocked: &[&Achievement],
+    ) {
         let game_complete_message: &str = match is_last_stage {
             true => "You Won! 🎉",
             false => "Stage complete! 💪",
         };
 
         self.render();
+        println!("All Stacks Sorted! - {}", game_complete_message);
+        if let Some(winner) = self.winner() {
+            println!("Player {} wins!", winner + 1);
+        }
         println!(
-            "All Stacks Sorted! - {}\nPress Enter to continue",
-            game_complete_message
+            "{}",
+            self.completion_summary(self.style.honoring_no_color())
         );
-        io::stdin().read_line(&mut String::new()).unwrap();
+        println!("Effort: {}", self.total_cost());
+        if is_new_best {
+            println!("NEW BEST!");
+        } else if let Some(best) = best {
+            let time: String = Game::format_elapsed(Duration::from_secs(best.time));
+            println!("Best: {} moves in {}", best.moves, time);
+        }
+        for achievement in unlocked {
+            println!(
+                "Achievement unlocked: {} {} {}",
+                achievement.name,
+                self.style.honoring_no_color().dash(),
+                achievement.description
+            );
+        }
+        Game::wait_for_enter();
+    }
+
+    pub fn confirm_retry(&self) -> bool {
+        let limit: usize = self.move_limit.unwrap_or_default();
+        self.confirm(&format!(
+            "Out of moves! The stage wasn't sorted within {} moves. Reset and try again? ('n' quits)",
+            limit
+        ))
+    }
+
+    pub fn parse_stage_choice(input: &str, stage_count: usize) -> Option<StageChoice> {
+        match input.trim() {
+            "t" => Some(StageChoice::Tutorial),
+            "a" => Some(StageChoice::All),
+            "d" => Some(StageChoice::Daily),
+            "q" => Some(StageChoice::Quit),
+            number => match number.parse::<usize>() {
+                Ok(num) if (1..=stage_count).contains(&num) => Some(StageChoice::Stage(num - 1)),
+                _ => None,
+            },
+        }
+    }
+
+    pub fn choose_stage(
+        stages: &[Game],
+        daily: &Game,
+        today: Date,
+        leaderboard: &Leaderboard,
+        style: RenderStyle,
+    ) -> StageChoice {
+        let style: RenderStyle = style.honoring_no_color();
+        let mut prompt: &str = "Choose a stage";
+        loop {
+            Game::clear_screen(style);
+            println!("{}\n", style.title());
+            println!(" t: Tutorial, learn how to play");
+            for (ind, stage) in stages.iter().enumerate() {
+                let completed: &str = match leaderboard.get(&stage.stage_name) {
+                    Some(_) => style.complete_marker(),
+                    None => "",
+                };
+                let difficulty: String = match stage.cached_difficulty() {
+                    Some(difficulty) => format!(" ({})", difficulty.get_name()),
+                    None => "".to_string(),
+                };
+                println!(
+                    "{:>2}: {}{}{}",
+                    ind + 1,
+                    stage.stage_name,
+                    difficulty,
+                    completed
+                );
+            }
+            println!(" a: Play all stages in order");
+            let daily_par: String = match daily.get_par() {
+                Some(par) => par.to_string(),
+                None => style.dash().to_string(),
+            };
+            let streak: String = match leaderboard.daily_streak(today) {
+                0 => "".to_string(),
+                days => format!(", {} day streak", days),
+            };
+            println!(" d: {} (par {}{})", daily.stage_name, daily_par, streak);
+            println!(" q: Quit\n");
+            print!("{}: ", prompt);
+            io::stdout().flush().unwrap();
+
+            let input: String = match input::read_line() {
+                Some(line) => line,
+                None => return StageChoice::Quit, // Input was closed.
+            };
+            match Game::parse_stage_choice(&input, stages.len()) {
+                Some(choice) => return choice,
+                None => {
+                    prompt = "Invalid choice! Please enter 't', a stage number, 'a', 'd' or 'q'"
+                }
+            }
+        }
+    }
+
+    pub fn help_text(keys: &KeyBindings) -> String {
+        let mut lines: Vec<String> = [
+            "Help:",
+            "",
+            "Rules:",
+            "  Units can be moved from one stack onto another if the top units of both stacks",
+            "  are of the same kind, or if the target stack is empty.",
+            "  As many of the top units as fit are poured, the rest stay where they were.",
+            "  A move onto a full stack is rejected and nothing changes.",
+            "  The stage is complete once each kind is gathered into a single stack of its own.",
+            "",
+            "Keys:",
+            "  '<from> <to>'  move the top units between stacks (e.g., '2 3')",
+            "  '<from>'       select a stack and see where it can pour, then enter the target",
+            "                 (Esc or an empty line cancels the selection); if only one stack can",
+            "                 take the units they pour there right away",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+        for (option, description) in [
+            (
+                MenuOption::Undo,
+                "undo the last move, returning its units to where they came from",
+            ),
+            (
+                MenuOption::UndoAll,
+                "undo every move back to the start, each can still be redone",
+            ),
+            (MenuOption::Redo, "redo the last undone move"),
+            (
+                MenuOption::Jump,
+                "jump to any point in the moves made and undone, each move back spends an undo",
+            ),
+            (
+                MenuOption::Reset,
+                "reset the stage to its starting layout, undo right after takes it back",
+            ),
+            (
+                MenuOption::Reshuffle,
+                "shuffle the same units into a new board, always one that can be solved",
+            ),
+            (MenuOption::Hint, "highlight a suggested next move"),
+            (
+                MenuOption::MovesLeft,
+                "tell how many moves an optimal solution still needs, but not which",
+            ),
+            (
+                MenuOption::Coach,
+                "point out moves that add a step to the best finish, slow on big stages",
+            ),
+            (
+                MenuOption::AutoSolve,
+                "watch the stage solve itself, press Enter to take over again",
+            ),
+            (MenuOption::Save, "save the game to continue it later"),
+            (MenuOption::History, "show or hide the list of recent moves"),
+            (MenuOption::Pause, "pause the game and its clock"),
+            (MenuOption::Help, "show this help"),
+            (MenuOption::Quit, "quit the game"),
+        ] {
+            let key: String = format!("'{}'", keys.key_for(option));
+            lines.push(format!("  {:<14} {}", key, description));
+        }
+        lines.push("".to_string());
+        lines.push("Viewing this help does not use up a turn.".to_string());
+        lines.join("\n")
     }
 
     pub fn show_help(&self) {
         self.render();
-        println!("Help:\n");
-        println!(
-            "Enter two numbers separated by a space to move units between stacks (e.g., '2 3')"
-        );
-        println!("Type 'u' to undo the last move");
-        println!("Type 'r' to reset the stage");
-        println!("Type 'q' to quit the game");
-        println!("Press Enter to continue");
-        io::stdin().read_line(&mut String::new()).unwrap();
+        println!("{}\n", Game::help_text(&self.keys));
+        Game::wait_for_enter();
     }
 
-    pub fn read_valid_input(&self) -> UserInput {
+    // Asks how many moves in to jump to, None once the player cancels with an empty line.
+    pub fn ask_jump_position(&self) -> Option<usize> {
+        let moves: usize = self.ledger.len() + self.redo_ledger.len();
+        loop {
+            self.render();
+            print!("Jump to after which move? (0-{}, Enter cancels): ", moves);
+            io::stdout().flush().unwrap();
+            let answer: String = input::read_line()?;
+            match answer.trim() {
+                "" => return None,
+                number => match number.parse::<usize>() {
+                    Ok(position) if position <= moves => return Some(position),
+                    _ => continue,
+                },
+            }
+        }
+    }
+
+    pub fn confirm(&self, question: &str) -> bool {
+        loop {
+            self.render();
+            print!("{} [y/n]: ", question);
+            io::stdout().flush().unwrap();
+            let answer: String = match input::read_line() {
+                Some(line) => line,
+                None => return true, // Input was closed, nothing left to wait for.
+            };
+            match answer.trim().to_lowercase().as_str() {
+                "y" | "yes" => return true,
+                "n" | "no" => return false,
+                _ => continue,
+            }
+        }
+    }
+
+    fn clear_screen(style: RenderStyle) {
+        match style.honoring_no_color() {
+            // Clear the screen and move the cursor to the top-left corner
+            RenderStyle::Color => print!("\x1B[2J\x1B[H"),
+            RenderStyle::Ascii => println!(),
+        }
+    }
+
+    pub fn restore_terminal(style: RenderStyle) {
+        // Reset any lingering colors and leave the cursor on a fresh line.
+        match style.honoring_no_color() {
+            RenderStyle::Color => println!("\x1b[0m"),
+            RenderStyle::Ascii => println!(),
+        }
+        io::stdout().flush().unwrap();
+    }
+
+    fn wait_for_enter() {
+        print!("Press Enter to continue");
+        io::stdout().flush().unwrap();
+        input::read_line();
+    }
+
+    // Redraws the board whenever the terminal width changes while waiting for the player.
+    fn prompt_until_entered(&self, prompt: &str) -> Option<String> {
+        let mut width: usize = layout::terminal_width();
+        self.render();
+        print!("{}: ", prompt);
+        loop {
+            io::stdout().flush().unwrap(); // Flush to ensure the message is displayed before reading input
+            match input::poll_line(RESIZE_POLL) {
+                input::Polled::Line(line) => return Some(line),
+                input::Polled::Closed => return None,
+                input::Polled::Idle => {
+                    let current_width: usize = layout::terminal_width();
+                    if current_width != width {
+                        width = current_width;
+                        self.render();
+                        print!("{}: ", prompt);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn read_valid_input(&mut self) -> UserInput {
         let mut user_input: UserInput = UserInput::new_menu_option(MenuOption::Help);
-        let mut input: String = String::new();
         let default_prompt: String = "Input move or type 'h' for help".to_string();
         let invalid_input_prompt: String = format!(
             "Invalid input!\nPlease enter two different numbers between 1 and {} separated by a space", self.stacks.len()
         );
         let mut current_prompt: String;
-        let mut next_prompt: String = String::new();
+        let mut next_prompt: String = self.message.take().unwrap_or_default(); // Shown once, then cleared.
 
         loop {
             if self.stage_complete() {
                 break;
             }
 
-            let no_legal_moves: bool = self.no_legal_moves();
-            if no_legal_moves {
-                next_prompt =
-                    "No legal moves left! - press 'u' to undo or 'r' to restart.".to_string();
-            }
-
             match next_prompt.len() {
                 0 => current_prompt = default_prompt.clone(),
                 _ => {
@@ -110,94 +692,535 @@ impl Game {
                 }
             }
 
-            self.render();
-            print!("{}: ", current_prompt);
-
-            let str_input: &str = match no_legal_moves {
-                // TODO: show help when no legal moves, handle flushing outside.
-                _ => {
-                    io::stdout().flush().unwrap(); // Flush to ensure the message is displayed before reading input
-                    input.clear();
-                    io::stdin().read_line(&mut input).unwrap();
-                    input.trim()
-                }
+            let input: String = match self.prompt_until_entered(&current_prompt) {
+                Some(line) => line,
+                None => return UserInput::new_menu_option(MenuOption::Quit), // Input was closed.
             };
+            let str_input: &str = input.trim();
 
-            user_input = match str_input {
-                "h" => UserInput::new_menu_option(MenuOption::Help),
-                "q" => UserInput::new_menu_option(MenuOption::Quit),
-                "r" => UserInput::new_menu_option(MenuOption::Reset),
-                "u" => UserInput::new_menu_option(MenuOption::Undo),
-                _ => {
-                    let parts: Vec<&str> = input.trim().split_whitespace().collect();
-                    if parts.len() != 2 {
-                        next_prompt = invalid_input_prompt.clone();
-                        continue;
-                    }
-
-                    let from = match parts[0].parse::<usize>() {
-                        Ok(num) if num - 1 < self.stacks.len() => num - 1,
-                        _ => {
-                            next_prompt = invalid_input_prompt.clone();
-                            continue;
+            let selected: Option<usize> = self.selected.take();
+            if selected.is_some() && (str_input == ESCAPE || str_input.is_empty()) {
+                continue; // Back to choosing a source.
+            }
+            user_input = match self.keys.option_for(str_input) {
+                Some(option) => UserInput::new_menu_option(option),
+                None if self.dev && str_input == DEV_SKIP => {
+                    UserInput::new_menu_option(MenuOption::Skip)
+                }
+                None => {
+                    let parts: Vec<&str> = input.split_whitespace().collect();
+                    let stack_count: usize = self.stacks.len();
+                    let stack_move: Option<(usize, usize)> = match (selected, parts.as_slice()) {
+                        (Some(from), [to]) => {
+                            Game::parse_stack_id(to, stack_count).map(|to| (from, to))
                         }
+                        (None, [from]) => match Game::parse_stack_id(from, stack_count) {
+                            Some(from) if self.destinations(from).is_empty() => {
+                                next_prompt = format!("Stack {} has nowhere to pour", from + 1);
+                                continue;
+                            }
+                            Some(from) => match self.only_destination(from) {
+                                Some(to) => Some((from, to)),
+                                None => {
+                                    self.selected = Some(from);
+                                    next_prompt = match self.tutorial_pour_prompt(from) {
+                                        Some(prompt) => prompt.to_string(),
+                                        None => format!(
+                                            "Pour stack {} into which stack? (Esc to cancel)",
+                                            from + 1
+                                        ),
+                                    };
+                                    continue;
+                                }
+                            },
+                            None => None,
+                        },
+                        (None, [from, to]) => Game::parse_stack_id(from, stack_count)
+                            .zip(Game::parse_stack_id(to, stack_count)),
+                        _ => None,
                     };
 
-                    let to = match parts[1].parse::<usize>() {
-                        Ok(num) if ((num - 1 < self.stacks.len()) && (num - 1 != from)) => num - 1,
+                    match stack_move {
+                        Some((from, to)) if from != to => UserInput::new_move(from, to),
                         _ => {
+                            self.selected = selected; // Keep the source while re-prompting.
                             next_prompt = invalid_input_prompt.clone();
                             continue;
                         }
-                    };
-
-                    if self.move_requires_more_room(from, to) {
-                        next_prompt =
-                            Game::illegal_move_prompt("Not enough room in the target stack");
-                        continue;
-                    }
-
-                    if self.stack_tops_mismatch(from, to) {
-                        next_prompt = Game::illegal_move_prompt(
-                            "Units can only be moved towards identical units, or empty stacks",
-                        );
-                        continue;
-                    }
-
-                    UserInput {
-                        menu_option: MenuOption::Move,
-                        stack_move: Some((from, to)),
                     }
                 }
             };
             break;
         }
-        return user_input;
+        user_input
+    }
+
+    // The target a source pours into without asking, when it has just one and that's enabled.
+    fn only_destination(&self, from: usize) -> Option<usize> {
+        match self.destinations(from).as_slice() {
+            [(to, _)] if !self.explicit_moves && !self.is_tutorial() => Some(*to), // The tutorial teaches both steps.
+            _ => None,
+        }
+    }
+
+    // Stack ids are however many digits the board needs, ended by Enter or a space ("12 3").
+    pub fn parse_stack_id(text: &str, stack_count: usize) -> Option<usize> {
+        match text.trim().parse::<usize>() {
+            Ok(num) if (1..=stack_count).contains(&num) => Some(num - 1),
+            _ => None,
+        }
     }
 
-    fn illegal_move_prompt(prompt: &str) -> String {
+    pub fn watch_auto_solve(&mut self) {
+        if self.has_face_down_units() {
+            self.message = Some(Game::face_down_prompt());
+            return;
+        }
+        let outcome: AutoSolveOutcome = self.auto_solve(|game| {
+            game.render();
+            print!("Solving... press Enter to stop");
+            io::stdout().flush().unwrap();
+            thread::sleep(AUTO_SOLVE_FRAME);
+            !input::line_pending()
+        });
+        self.message = match outcome {
+            AutoSolveOutcome::Solved => None,
+            AutoSolveOutcome::Stopped => Some("Stopped solving, undo to go back".to_string()),
+            AutoSolveOutcome::NoSolution => Some("No solution found".to_string()),
+        };
+    }
+
+    pub fn watch_undo_all(&mut self) {
+        self.undo_all(|game| {
+            game.render();
+            io::stdout().flush().unwrap();
+            thread::sleep(UNDO_ALL_FRAME);
+        });
+    }
+
+    pub fn watch_fast_forward(&mut self) {
+        let made: usize = self.fast_forward(|game| {
+            game.render();
+            io::stdout().flush().unwrap();
+            thread::sleep(FAST_FORWARD_FRAME);
+        });
+        if made > 0 {
+            self.message = Some(match made {
+                1 => "Played 1 forced move".to_string(),
+                made => format!("Played {} forced moves", made),
+            });
+        }
+    }
+
+    // What the player is about to take on, and what there is to beat.
+    pub fn intro_text(&self, style: RenderStyle, best: Option<&BestScore>) -> String {
+        let mut text: String = format!("{}\n\n{}\n", style.title(), self.stage_name);
+        if let Some(author) = &self.meta.author {
+            let _ = writeln!(text, "by {}", author);
+        }
+        if let Some(description) = &self.meta.description {
+            let _ = writeln!(text, "\n{}", description);
+        }
+        let par: String = match self.get_par() {
+            Some(par) => format!("{} moves", par),
+            None => style.dash().to_string(),
+        };
+        let best: String = match best {
+            Some(best) => format!(
+                "{} moves in {}",
+                best.moves,
+                Game::format_elapsed(Duration::from_secs(best.time))
+            ),
+            None => "not played yet".to_string(),
+        };
+        let _ = writeln!(text, "\nPar: {}\nBest: {}", par, best);
+        text
+    }
+
+    pub fn show_intro(&self, best: Option<&BestScore>) {
+        let style: RenderStyle = self.style.honoring_no_color();
+        Game::clear_screen(style);
+        println!("{}", self.intro_text(style, best));
+        print!("Press Enter to begin");
+        io::stdout().flush().unwrap();
+        input::read_line();
+    }
+
+    // The board is hidden while paused, so a pause can't be used to think on the clock's time.
+    pub fn show_pause_screen(&mut self) {
+        self.pause();
+        let style: RenderStyle = self.style.honoring_no_color();
+        Game::clear_screen(style);
+        println!("{}\n", style.title());
+        print!("Paused {} press Enter to resume", style.dash());
+        io::stdout().flush().unwrap();
+        input::read_line();
+        self.resume();
+    }
+
+    // Stacks light up one after another, then keep twinkling until the animation ends.
+    pub fn win_animation_frame(&self, frame: usize, width: usize) -> String {
+        let cells: Vec<String> = (0..self.stacks.len())
+            .map(|stack_ind| {
+                let line: String = self.format_stack(stack_ind, true);
+                match stack_ind <= frame {
+                    true => format!(
+                        "{} \x1b[1;33m{}\x1b[0m",
+                        line,
+                        SPARKLES[(frame + stack_ind) % SPARKLES.len()]
+                    ),
+                    false => line,
+                }
+            })
+            .collect();
+        format!(
+            "{}\n{}\n\n{}",
+            RenderStyle::Color.title(),
+            self.stage_name,
+            layout::grid(&cells, width)
+        )
+    }
+
+    // Only in color, and not when asked to be quiet; Enter skips it.
+    pub fn play_win_animation(&self) {
+        if self.quiet || self.style.honoring_no_color() != RenderStyle::Color {
+            return;
+        }
+        let frames: usize = WIN_FRAMES.max(self.stacks.len() + SPARKLES.len());
+        for frame in 0..frames {
+            Game::clear_screen(RenderStyle::Color);
+            print!(
+                "{}",
+                self.win_animation_frame(frame, layout::terminal_width())
+            );
+            io::stdout().flush().unwrap();
+            thread::sleep(WIN_FRAME);
+            if input::line_pending() {
+                break;
+            }
+        }
+    }
+
+    pub fn ring_bell() {
+        print!("\x07");
+        io::stdout().flush().unwrap();
+    }
+
+    pub fn illegal_move_prompt(prompt: &str) -> String {
         format!("Illegal move!\n{}.\nplease try again", prompt)
     }
+
+    pub fn face_down_prompt() -> String {
+        "No hints while units are face down, that would give them away".to_string()
+    }
 }
 
-#[allow(dead_code)]
-const COLORS: [[u8; 3]; 11] = [
-    [255, 0, 0],
-    [0, 255, 0],
-    [0, 0, 255],
-    [255, 255, 0],
-    [0, 255, 255],
-    [255, 0, 255],
-    // [127, 255, 0],
-    // [0, 127, 255],
-    // [255, 0, 127],
-    [255, 127, 0],
-    [0, 255, 127],
-    // [127, 0, 255],
-    [255, 127, 127],
-    // [127, 255, 127],
-    // [127, 127, 255],
-    [127, 127, 127],
-    [255, 255, 255],
-    // [0, 0, 0],
-];
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stage_choice() {
+        assert_eq!(
+            Game::parse_stage_choice("2\n", 3),
+            Some(StageChoice::Stage(1))
+        );
+        assert_eq!(Game::parse_stage_choice("a", 3), Some(StageChoice::All));
+        assert_eq!(Game::parse_stage_choice("q", 3), Some(StageChoice::Quit));
+        assert_eq!(Game::parse_stage_choice("d", 3), Some(StageChoice::Daily));
+        assert_eq!(
+            Game::parse_stage_choice("t", 3),
+            Some(StageChoice::Tutorial)
+        );
+        assert_eq!(Game::parse_stage_choice("0", 3), None);
+        assert_eq!(Game::parse_stage_choice("4", 3), None);
+    }
+
+    #[test]
+    fn test_parse_multi_digit_stack_ids() {
+        assert_eq!(Game::parse_stack_id("10", 12), Some(9));
+        assert_eq!(Game::parse_stack_id("3", 12), Some(2));
+        assert_eq!(Game::parse_stack_id("12\n", 12), Some(11));
+        assert_eq!(Game::parse_stack_id("99", 12), None);
+        assert_eq!(Game::parse_stack_id("13", 12), None);
+        assert_eq!(Game::parse_stack_id("0", 12), None);
+        assert_eq!(Game::parse_stack_id("1 2", 12), None);
+    }
+
+    #[test]
+    fn test_render_small_board() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 0]], Some("Tiny".to_string()));
+        game.move_legally(0, 1);
+        game.highlight = Some((1, 0));
+        let red: &str = "\x1b[38;2;255;0;0m";
+        let green: &str = "\x1b[38;2;0;255;0m";
+        let expected: String = [
+            "|**************|",
+            "| Sorting Game |",
+            "****************",
+            "Tiny",
+            "Turn - 2 | moves: 1 / par —",
+            &format!("Kinds: {}1\x1b[0m ✓  {}2\x1b[0m ✓", red, green),
+            "",
+            &format!("\x1b[1m 1\x1b[0m: {} 1\x1b[0m __ __  ✓ <- pour here", red),
+            &format!(
+                "\x1b[1m 2\x1b[0m: {} 2\x1b[0m {} 2\x1b[0m  ✓ <- take from here",
+                green, green
+            ),
+            "",
+            "",
+        ]
+        .join("\n");
+        // Narrow enough that each stack gets its own row.
+        assert_eq!(
+            game.render_to_string_with_width(RenderStyle::Color, 40),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_render_shows_progress_through_stages() {
+        let mut game: Game = Game::new_from_vecs(vec![vec![1, 0], vec![1, 0]], None);
+        assert!(!game
+            .render_to_string_with_width(RenderStyle::Ascii, 80)
+            .contains("Stage "));
+        game.progress = Some((3, 4));
+        let frame: String = game.render_to_string_with_width(RenderStyle::Ascii, 80);
+        assert!(frame.contains("Stage 3 / 4 [#####.....]"));
+    }
+
+    #[test]
+    fn test_render_wraps_stacks_into_a_grid() {
+        let game: Game = Game::new_from_vecs(vec![vec![1, 0]; 7], None);
+        // Each ASCII stack reads " 1: |A .|", nine columns wide.
+        let frame: String = game.render_to_string_with_width(RenderStyle::Ascii, 40);
+        let rows: Vec<&str> = frame.lines().filter(|line| line.contains(": |")).collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0], " 1: |A .|    2: |A .|    3: |A .|");
+        assert_eq!(rows[2], " 7: |A .|");
+        assert_eq!(rows[0].len(), 33);
+    }
+
+    #[test]
+    fn test_empty_stacks_are_dotted_and_numbered() {
+        let mut game: Game = Game::new_from_vecs(vec![vec![1, 0, 0], vec![0, 0, 0]], None);
+        let frame: String = game.render_to_string_with_width(RenderStyle::Ascii, 40);
+        assert!(frame.contains(" 1: |A . .|"));
+        assert!(frame.contains(" 2: |: : :| empty"));
+        let frame: String = game.render_to_string_with_width(RenderStyle::Color, 40);
+        assert!(frame.contains(" 2: ┄┄ ┄┄ ┄┄  empty"));
+
+        game.empty_look = EmptyStackLook::Plain;
+        let frame: String = game.render_to_string_with_width(RenderStyle::Ascii, 40);
+        assert!(frame.contains(" 2: |. . .|\n"));
+    }
+
+    #[test]
+    fn test_compact_render_counts_runs() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![1, 1, 2, 0], vec![2, 1, 1, 1], vec![0, 0, 0, 0]],
+            None,
+        );
+        game.compact_runs = true;
+        let frame: String = game.render_to_string_with_width(RenderStyle::Ascii, 40);
+        assert!(frame.contains(" 1: |Ax2 B .|"));
+        assert!(frame.contains(" 2: |B Ax3|"));
+        let frame: String = game.render_to_string_with_width(RenderStyle::Color, 40);
+        assert!(frame.contains(" 1×2\x1b[0m"));
+
+        game.compact_runs = false;
+        let frame: String = game.render_to_string_with_width(RenderStyle::Ascii, 40);
+        assert!(frame.contains(" 2: |B A A A|"));
+    }
+
+    #[test]
+    fn test_labeled_stack_shows_its_label() {
+        let game: Game = Game::parse_stage("label: 3 Sort here\n\nAB.\nBA.\n...\n").unwrap();
+        let frame: String = game.render_to_string_with_width(RenderStyle::Ascii, 40);
+        assert!(frame.contains(" 3: |: : :| empty - Sort here"));
+        assert!(!frame.contains(" 1: |A B .| -"));
+        let frame: String = game.render_to_string_with_width(RenderStyle::Color, 40);
+        assert!(frame.contains(" — Sort here"));
+    }
+
+    #[test]
+    fn test_intro_text() {
+        let mut game: Game = Game::parse_stage(
+            "name: Warm up\nauthor: Sam\ndescription: Mind the spare stack\npar: 3\n\nAB.\nBA.\n...\n",
+        )
+        .unwrap();
+        game.settle_par();
+        let best: BestScore = BestScore { moves: 4, time: 75 };
+        let intro: String = game.intro_text(RenderStyle::Ascii, Some(&best));
+        assert!(intro.contains("Warm up\nby Sam\n\nMind the spare stack\n"));
+        assert!(intro.ends_with("Par: 3 moves\nBest: 4 moves in 1:15\n"));
+
+        let plain: Game = Game::parse_stage("name: Warm up\n\nAB.\nBA.\n...\n").unwrap();
+        let intro: String = plain.intro_text(RenderStyle::Ascii, None);
+        assert!(intro.contains("Warm up\n\nPar: -\nBest: not played yet\n"));
+    }
+
+    #[test]
+    fn test_ascii_render_has_no_escape_codes() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 0]], Some("Tiny".to_string()));
+        game.move_legally(0, 1);
+        game.highlight = Some((1, 0));
+        let frame: String = game.render_to_string(RenderStyle::Ascii);
+        assert!(frame.is_ascii());
+        assert!(!frame.contains('\x1b'));
+        assert!(frame.contains("+--------------+"));
+        assert!(frame.contains(" 1: |A . .| * <- pour here"));
+        assert!(frame.contains(" 2: |B B| * <- take from here"));
+        assert!(frame.contains("par -"));
+        assert!(frame.contains("Kinds: A *  B *"));
+    }
+
+    #[test]
+    fn test_selection_highlights_legal_destinations() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![
+                vec![1, 1, 0],
+                vec![2, 1, 0, 0],
+                vec![2, 0, 0],
+                vec![0, 0, 0],
+            ],
+            None,
+        );
+        game.selected = Some(1);
+        let legal_targets: Vec<usize> = game
+            .legal_moves()
+            .into_iter()
+            .filter(|(from, _)| *from == 1)
+            .map(|(_, to)| to)
+            .collect();
+        assert_eq!(legal_targets, vec![0, 3]);
+        let highlighted: Vec<usize> = (0..game.stacks.len())
+            .filter(|stack_ind| game.highlight_marker(*stack_ind).contains("pours"))
+            .collect();
+        assert_eq!(highlighted, legal_targets);
+        assert_eq!(game.destinations(1), vec![(0, 1), (3, 1)]);
+        assert_eq!(game.highlight_marker(1), " <- selected");
+    }
+
+    #[test]
+    fn test_unique_destination_is_taken_directly() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![1, 1, 0], vec![2, 1, 0, 0], vec![2, 0, 0], vec![3, 0]],
+            None,
+        );
+        assert_eq!(game.destinations(1), vec![(0, 1)]);
+        assert_eq!(game.only_destination(1), Some(0));
+
+        game.explicit_moves = true;
+        assert_eq!(game.only_destination(1), None);
+
+        let two_targets: Game = Game::new_from_vecs(
+            vec![vec![1, 1, 0], vec![2, 1, 0, 0], vec![2, 0, 0], vec![0, 0]],
+            None,
+        );
+        assert_eq!(two_targets.destinations(1).len(), 2);
+        assert_eq!(two_targets.only_destination(1), None);
+    }
+
+    #[test]
+    fn test_history_panel_follows_the_ledger() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0], vec![0, 0, 0]],
+            None,
+        );
+        let frame: String = game.render_to_string_with_width(RenderStyle::Ascii, 80);
+        assert!(!frame.contains("History:"));
+
+        game.show_history = true;
+        game.move_legally(0, 2);
+        game.move_legally(1, 3);
+        let frame: String = game.render_to_string_with_width(RenderStyle::Ascii, 80);
+        let first: usize = frame.find("2->4 x1 (A)").unwrap();
+        assert!(first < frame.find("1->3 x1 (B)").unwrap()); // Most recent first.
+
+        game.undo_move();
+        let frame: String = game.render_to_string_with_width(RenderStyle::Ascii, 80);
+        assert!(frame.contains("History:"));
+        assert!(frame.contains("1->3 x1 (B)"));
+        assert!(!frame.contains("2->4"));
+        assert!(game
+            .render_to_string_with_width(RenderStyle::Color, 80)
+            .contains("1→3 ×1 (2)"));
+    }
+
+    #[test]
+    fn test_win_animation_cascades_through_stacks() {
+        let game: Game = Game::new_from_vecs(
+            vec![vec![1, 1], vec![2, 2], vec![0, 0]],
+            Some("Won".to_string()),
+        );
+        let lit = |frame: &str| {
+            frame
+                .lines()
+                .filter(|line| line.contains("\x1b[1;33m"))
+                .count()
+        };
+
+        let first: String = game.win_animation_frame(0, 1);
+        assert!(first.contains("Won"));
+        assert_eq!(lit(&first), 1);
+        assert!(first.contains(SPARKLES[0]));
+        assert_eq!(lit(&game.win_animation_frame(1, 1)), 2);
+        let last: String = game.win_animation_frame(WIN_FRAMES - 1, 1);
+        assert_eq!(lit(&last), 3);
+        assert_ne!(last, game.win_animation_frame(WIN_FRAMES, 1)); // Still twinkling.
+    }
+
+    #[test]
+    fn test_only_finished_stacks_are_marked() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![1, 1, 0], vec![2, 1, 0], vec![2, 0, 0], vec![0, 0, 0]],
+            None,
+        );
+        game.move_legally(1, 0);
+        let completed: Vec<usize> = game.completed_stacks();
+        assert_eq!(completed, vec![0]);
+
+        for stack_ind in 0..game.stacks.len() {
+            let line: String = game.format_stack(stack_ind, completed.contains(&stack_ind));
+            let marker: &str = RenderStyle::Color.complete_marker();
+            assert_eq!(line.contains(marker), stack_ind == 0, "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_face_down_units_are_not_marked_done() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![2, 1, 1], vec![3, 3, 0], vec![2, 0, 0], vec![0, 0, 0]],
+            None,
+        );
+        assert_eq!(game.shown_completed_stacks(), vec![1]);
+        assert_eq!(game.shown_completed_kinds().len(), 2);
+
+        game.hide_units();
+        assert!(game.shown_completed_stacks().is_empty());
+        assert!(game.shown_completed_kinds().is_empty());
+        let frame: String = game.render_to_string_with_width(RenderStyle::Ascii, 80);
+        assert!(
+            !frame.contains(RenderStyle::Ascii.complete_marker()),
+            "{}",
+            frame
+        );
+
+        game.run_script(&[(0, 3), (0, 3)]).unwrap(); // Gathers the 1s again, face up.
+        assert_eq!(game.shown_completed_kinds(), vec![Kind::new(1, 2)]);
+        assert_eq!(game.shown_completed_stacks(), vec![3]);
+    }
+
+    #[test]
+    fn test_help_text_lists_every_key() {
+        let help: String = Game::help_text(&KeyBindings::default());
+        for key in [
+            "'u'", "'y'", "'r'", "'s'", "'t'", "'a'", "'l'", "'p'", "'h'", "'q'", "'2 3'",
+        ] {
+            assert!(help.contains(key), "help is missing {}", key);
+        }
+    }
+}
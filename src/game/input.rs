@@ -0,0 +1,135 @@
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, TryRecvError};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+// Stdin is read on a background thread, so the game can check for a pressed Enter key while
+// it is busy (e.g. animating) without blocking, and without a stray reader thread stealing
+// the next line afterwards.
+static LINES: OnceLock<Mutex<Receiver<String>>> = OnceLock::new();
+
+fn lines() -> &'static Mutex<Receiver<String>> {
+    LINES.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<String>();
+        thread::spawn(move || {
+            for line in io::stdin().lock().lines() {
+                let Ok(line) = line else { break };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Mutex::new(receiver)
+    })
+}
+
+thread_local! {
+    // While set, lines come from here instead of stdin, so a whole session can be scripted.
+    static SCRIPT: RefCell<Option<VecDeque<String>>> = const { RefCell::new(None) };
+    static CLOSED: Cell<bool> = const { Cell::new(false) };
+}
+
+// Runs `run` with its input read from `lines`, the input counts as closed once they run out.
+pub fn with_script<T>(lines: Vec<String>, run: impl FnOnce() -> T) -> T {
+    SCRIPT.with(|script| *script.borrow_mut() = Some(lines.into()));
+    CLOSED.with(|closed| closed.set(false));
+    let result: T = run();
+    SCRIPT.with(|script| *script.borrow_mut() = None);
+    result
+}
+
+// The next scripted line, or None when no script is running.
+fn next_scripted() -> Option<Option<String>> {
+    SCRIPT.with(|script| {
+        let mut script = script.borrow_mut();
+        script.as_mut().map(|lines| lines.pop_front())
+    })
+}
+
+fn closed_if_none(line: Option<String>) -> Option<String> {
+    if line.is_none() {
+        CLOSED.with(|closed| closed.set(true));
+    }
+    line
+}
+
+// Whether a read has found the input closed.
+pub fn is_closed() -> bool {
+    CLOSED.with(|closed| closed.get())
+}
+
+// Blocks until a line is entered, None once the input is closed.
+pub fn read_line() -> Option<String> {
+    if let Some(line) = next_scripted() {
+        return closed_if_none(line);
+    }
+    closed_if_none(
+        lines()
+            .lock()
+            .ok()
+            .and_then(|receiver| receiver.recv().ok()),
+    )
+}
+
+pub enum Polled {
+    Line(String),
+    Idle,
+    Closed,
+}
+
+// Waits at most `timeout` for a line, so the caller can do other work between checks.
+pub fn poll_line(timeout: Duration) -> Polled {
+    if let Some(line) = next_scripted() {
+        return match closed_if_none(line) {
+            Some(line) => Polled::Line(line),
+            None => Polled::Closed,
+        };
+    }
+    let Ok(receiver) = lines().lock() else {
+        return Polled::Closed;
+    };
+    match receiver.recv_timeout(timeout) {
+        Ok(line) => Polled::Line(line),
+        Err(RecvTimeoutError::Timeout) => Polled::Idle,
+        Err(RecvTimeoutError::Disconnected) => {
+            closed_if_none(None);
+            Polled::Closed
+        }
+    }
+}
+
+// Consumes a line if one was entered since the last read, without waiting for one.
+pub fn line_pending() -> bool {
+    if SCRIPT.with(|script| script.borrow().is_some()) {
+        return false; // A script is read line by line, never skipped ahead.
+    }
+    match lines().lock() {
+        Ok(receiver) => match receiver.try_recv() {
+            Ok(_) => true,
+            Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => false,
+        },
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_is_read_then_closed() {
+        let lines: Vec<String> = vec!["1".to_string(), "q".to_string()];
+        let read: Vec<Option<String>> = with_script(lines, || {
+            assert!(!is_closed());
+            (0..3).map(|_| read_line()).collect()
+        });
+        assert_eq!(
+            read,
+            vec![Some("1".to_string()), Some("q".to_string()), None]
+        );
+        assert!(is_closed());
+    }
+}
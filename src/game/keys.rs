@@ -0,0 +1,176 @@
+use crate::game::config;
+use crate::game::error::KeyBindingError;
+use crate::game::gui::MenuOption;
+use std::fs;
+
+// Every rebindable action, with the name used in the config file and its default key.
+const ACTIONS: [(&str, MenuOption, char); 15] = [
+    ("help", MenuOption::Help, 'h'),
+    ("quit", MenuOption::Quit, 'q'),
+    ("reset", MenuOption::Reset, 'r'),
+    ("undo", MenuOption::Undo, 'u'),
+    ("undo_all", MenuOption::UndoAll, 'b'),
+    ("redo", MenuOption::Redo, 'y'),
+    ("jump", MenuOption::Jump, 'j'),
+    ("save", MenuOption::Save, 's'),
+    ("hint", MenuOption::Hint, 't'),
+    ("moves_left", MenuOption::MovesLeft, 'm'),
+    ("coach", MenuOption::Coach, 'c'),
+    ("reshuffle", MenuOption::Reshuffle, 'n'),
+    ("auto_solve", MenuOption::AutoSolve, 'a'),
+    ("history", MenuOption::History, 'l'),
+    ("pause", MenuOption::Pause, 'p'),
+];
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct KeyBindings {
+    keys: Vec<(MenuOption, char)>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> KeyBindings {
+        KeyBindings {
+            keys: ACTIONS
+                .iter()
+                .map(|(_, option, key)| (*option, *key))
+                .collect(),
+        }
+    }
+}
+
+impl KeyBindings {
+    // Reads the user's key file if there is one, the defaults otherwise.
+    pub fn load_user_config() -> Result<KeyBindings, KeyBindingError> {
+        match config::keys_path().filter(|path| path.is_file()) {
+            Some(path) => KeyBindings::parse(&fs::read_to_string(path)?),
+            None => Ok(KeyBindings::default()),
+        }
+    }
+
+    // Each line rebinds one action, e.g. `undo = z`. Actions left out keep their default key.
+    // Blank lines and `#` comments are skipped.
+    pub fn parse(text: &str) -> Result<KeyBindings, KeyBindingError> {
+        let mut bindings: KeyBindings = KeyBindings::default();
+        for (line_index, line) in text.lines().enumerate() {
+            let line_number: usize = line_index + 1;
+            let line: &str = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((action, key)) = line.split_once('=') else {
+                return Err(KeyBindingError::Syntax {
+                    line: line_number,
+                    text: line.to_string(),
+                });
+            };
+            let (action, key) = (action.trim(), key.trim());
+            let Some((_, option, _)) = ACTIONS.iter().find(|(name, _, _)| *name == action) else {
+                return Err(KeyBindingError::UnknownAction {
+                    line: line_number,
+                    action: action.to_string(),
+                });
+            };
+            let mut characters = key.chars();
+            let key_char: char = match (characters.next(), characters.next()) {
+                (Some(key_char), None) if !key_char.is_ascii_digit() => key_char,
+                _ => {
+                    return Err(KeyBindingError::InvalidKey {
+                        line: line_number,
+                        key: key.to_string(),
+                    })
+                }
+            };
+            for binding in bindings.keys.iter_mut() {
+                if binding.0 == *option {
+                    binding.1 = key_char;
+                }
+            }
+        }
+        bindings.check_conflicts()?;
+        Ok(bindings)
+    }
+
+    fn check_conflicts(&self) -> Result<(), KeyBindingError> {
+        for (ind, (option, key)) in self.keys.iter().enumerate() {
+            if let Some((other, _)) = self.keys[ind + 1..]
+                .iter()
+                .find(|(_, other_key)| other_key == key)
+            {
+                return Err(KeyBindingError::Conflict {
+                    key: *key,
+                    first: KeyBindings::action_name(*option).to_string(),
+                    second: KeyBindings::action_name(*other).to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn action_name(option: MenuOption) -> &'static str {
+        ACTIONS
+            .iter()
+            .find(|(_, action, _)| *action == option)
+            .map_or("?", |(name, _, _)| name)
+    }
+
+    pub(crate) fn key_for(&self, option: MenuOption) -> char {
+        self.keys
+            .iter()
+            .find(|(action, _)| *action == option)
+            .map_or('?', |(_, key)| *key)
+    }
+
+    pub(crate) fn option_for(&self, input: &str) -> Option<MenuOption> {
+        let mut characters = input.chars();
+        let key: char = match (characters.next(), characters.next()) {
+            (Some(key), None) => key,
+            _ => return None,
+        };
+        self.keys
+            .iter()
+            .find(|(_, bound)| *bound == key)
+            .map(|(option, _)| *option)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_match_the_original_keys() {
+        let bindings: KeyBindings = KeyBindings::default();
+        assert_eq!(bindings.option_for("u"), Some(MenuOption::Undo));
+        assert_eq!(bindings.option_for("q"), Some(MenuOption::Quit));
+        assert_eq!(bindings.option_for("x"), None);
+        assert_eq!(bindings.option_for("uu"), None);
+    }
+
+    #[test]
+    fn test_remapped_undo_key() {
+        let bindings: KeyBindings = KeyBindings::parse("# mine\nundo = z\n").unwrap();
+        assert_eq!(bindings.option_for("z"), Some(MenuOption::Undo));
+        assert_eq!(bindings.option_for("u"), None);
+        assert_eq!(bindings.key_for(MenuOption::Undo), 'z');
+    }
+
+    #[test]
+    fn test_reject_bad_bindings() {
+        assert!(matches!(
+            KeyBindings::parse("undo = h"),
+            Err(KeyBindingError::Conflict { key: 'h', .. })
+        ));
+        assert!(matches!(
+            KeyBindings::parse("teleport = x"),
+            Err(KeyBindingError::UnknownAction { line: 1, .. })
+        ));
+        assert!(matches!(
+            KeyBindings::parse("undo = 3"),
+            Err(KeyBindingError::InvalidKey { .. })
+        ));
+        assert!(matches!(
+            KeyBindings::parse("undo z"),
+            Err(KeyBindingError::Syntax { .. })
+        ));
+    }
+}
@@ -0,0 +1,113 @@
+use std::env;
+use terminal_size::{terminal_size, Width};
+
+pub const DEFAULT_WIDTH: usize = 80;
+pub const COLUMN_GAP: usize = 3;
+
+// Queried on every call, so a resized terminal is picked up by the next frame.
+pub fn terminal_width() -> usize {
+    if let Some((Width(columns), _)) = terminal_size().filter(|(Width(columns), _)| *columns > 0) {
+        return columns as usize;
+    }
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+// The width of the text as shown, skipping `ESC [ ... m` color sequences.
+pub fn visible_width(text: &str) -> usize {
+    let mut width: usize = 0;
+    let mut in_escape: bool = false;
+    for character in text.chars() {
+        match (in_escape, character) {
+            (false, '\x1b') => in_escape = true,
+            (false, _) => width += 1,
+            (true, 'm') => in_escape = false,
+            (true, _) => {}
+        }
+    }
+    width
+}
+
+pub fn column_count(cell_width: usize, width: usize) -> usize {
+    ((width + COLUMN_GAP) / (cell_width + COLUMN_GAP)).max(1)
+}
+
+// Lays the cells out row by row, padding each column to the widest cell.
+pub fn grid(cells: &[String], width: usize) -> String {
+    let cell_width: usize = cells
+        .iter()
+        .map(|cell| visible_width(cell))
+        .max()
+        .unwrap_or(0);
+    let columns: usize = column_count(cell_width, width);
+    let mut text: String = String::new();
+    for row in cells.chunks(columns) {
+        for (ind, cell) in row.iter().enumerate() {
+            text.push_str(cell);
+            if ind + 1 < row.len() {
+                let padding: usize = cell_width - visible_width(cell) + COLUMN_GAP;
+                text.push_str(&" ".repeat(padding));
+            }
+        }
+        text.push('\n');
+    }
+    text
+}
+
+// Puts the right-hand lines next to the left-hand text, which keeps its own line breaks.
+pub fn side_by_side(left: &str, right: &[String]) -> String {
+    let left_lines: Vec<&str> = left.lines().collect();
+    let left_width: usize = left_lines
+        .iter()
+        .map(|line| visible_width(line))
+        .max()
+        .unwrap_or(0);
+    let mut text: String = String::new();
+    for ind in 0..left_lines.len().max(right.len()) {
+        let left_line: &str = left_lines.get(ind).copied().unwrap_or("");
+        text.push_str(left_line);
+        if let Some(right_line) = right.get(ind) {
+            let padding: usize = left_width - visible_width(left_line) + COLUMN_GAP;
+            text.push_str(&" ".repeat(padding));
+            text.push_str(right_line);
+        }
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visible_width_skips_colors() {
+        assert_eq!(visible_width("\x1b[38;2;255;0;0m 1\x1b[0m __"), 5);
+        assert_eq!(visible_width(" 1: |A .| ✓"), 11);
+    }
+
+    #[test]
+    fn test_grid_pads_columns() {
+        let cells: Vec<String> = ["a", "bbb", "cc"].iter().map(|c| c.to_string()).collect();
+        assert_eq!(grid(&cells, 9), "a     bbb\ncc\n");
+        assert_eq!(grid(&cells, 80), "a     bbb   cc\n");
+    }
+
+    #[test]
+    fn test_side_by_side() {
+        let right: Vec<String> = vec!["x".to_string(), "y".to_string(), "z".to_string()];
+        assert_eq!(side_by_side("ab\nc\n", &right), "ab   x\nc    y\n     z\n");
+    }
+
+    #[test]
+    fn test_grid_tolerates_tiny_widths() {
+        let cells: Vec<String> = ["a", "bbb", "cc"].iter().map(|c| c.to_string()).collect();
+        for width in [0, 1] {
+            assert_eq!(column_count(3, width), 1);
+            assert_eq!(grid(&cells, width), "a\nbbb\ncc\n");
+        }
+        assert_eq!(grid(&[], 1), "");
+    }
+}
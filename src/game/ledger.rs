@@ -0,0 +1,244 @@
+use crate::game::entry::Entry;
+use crate::game::error::ReplayError;
+use crate::game::observer::MoveEvent;
+use crate::game::Game;
+use std::io::{self, Write};
+
+const CSV_HEADER: &str = "move_number,from,to,kind,quantity";
+
+impl Game {
+    // Stacks are numbered from 1, as they are on screen.
+    pub fn export_ledger_csv(&self, w: &mut impl Write) -> io::Result<()> {
+        writeln!(w, "{}", CSV_HEADER)?;
+        for (ind, entry) in self.ledger.iter().enumerate() {
+            writeln!(
+                w,
+                "{},{},{},{},{}",
+                ind + 1,
+                entry.from + 1,
+                entry.to + 1,
+                entry.kind.get_id(),
+                entry.quantity()
+            )?;
+        }
+        Ok(())
+    }
+
+    // Back-to-back entries pouring the same kind between the same stacks read as one move.
+    // Only a view: undo keeps working through the raw ledger.
+    pub fn compacted_ledger(&self) -> Vec<Entry> {
+        let mut compacted: Vec<Entry> = Vec::new();
+        for entry in &self.ledger {
+            match compacted.last_mut() {
+                Some(last)
+                    if last.from == entry.from
+                        && last.to == entry.to
+                        && last.kind.get_id() == entry.kind.get_id()
+                        && last.player == entry.player =>
+                {
+                    last.kind.set_quantity(last.quantity() + entry.quantity());
+                }
+                _ => compacted.push(entry.clone()),
+            }
+        }
+        compacted
+    }
+
+    // Moves through the history to just after `position` moves, counting the undone ones that
+    // can still be redone. Nothing is lost until a new move is made, but every move jumped back
+    // over costs an undo, so a jump is refused where the undos wouldn't cover it.
+    pub fn jump_to(&mut self, position: usize) -> bool {
+        if position > self.ledger.len() + self.redo_ledger.len() {
+            return false;
+        }
+        let back: usize = self.ledger.len().saturating_sub(position);
+        if back > 0 && self.two_player {
+            self.message = Some("Undo is off in two-player games".to_string());
+            return false;
+        }
+        if self.undos_left().is_some_and(|left| left < back) {
+            self.message = Some(format!(
+                "Jumping back {} moves needs more undos than are left",
+                back
+            ));
+            return false;
+        }
+        while self.ledger.len() > position {
+            let entry: Entry = self
+                .ledger
+                .pop()
+                .expect("the ledger is longer than position");
+            if !self.take_back(&entry) {
+                self.ledger.push(entry);
+                return false;
+            }
+            self.undos_used += 1;
+            self.undo_count += 1;
+            self.redo_ledger.push(entry);
+        }
+        while self.ledger.len() < position {
+            self.redo_move();
+        }
+        true
+    }
+
+    // The board is only changed once every entry has been accepted, and only then is the
+    // observer told of each move, as the copy the entries were tried on has none.
+    pub fn replay(&mut self, entries: &[Entry]) -> Result<(), ReplayError> {
+        let mut replayed: Game = self.clone();
+        for (index, entry) in entries.iter().enumerate() {
+            replayed
+                .try_move(entry.from, entry.to)
+                .map_err(|error| ReplayError { index, error })?;
+        }
+        let made: usize = self.ledger.len();
+        replayed.observer = std::mem::take(&mut self.observer);
+        *self = replayed;
+        let added: Vec<Entry> = self.ledger[made..].to_vec();
+        for entry in added {
+            self.notify(MoveEvent::Move(entry));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::error::MoveError;
+    use crate::game::script::ScriptReport;
+    use crate::game::stack::kind::Kind;
+
+    #[test]
+    fn test_compacted_ledger_merges_repeated_pours() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![2, 1, 1], vec![1, 0, 0], vec![2, 0, 0]], None);
+        // Face down, a pour only lifts the units showing, so the 1s leave one at a time.
+        game.hide_units();
+        let report: ScriptReport = game.run_script(&[(0, 1), (0, 1), (0, 2)]).unwrap();
+        assert_eq!(report.accepted, 3);
+
+        let compacted: Vec<Entry> = game.compacted_ledger();
+        assert_eq!(compacted.len(), 2);
+        assert_eq!(compacted[0].quantity(), 2);
+        assert_eq!(compacted[0].kind, Kind::new(1, 2));
+        assert_eq!(compacted[1], game.ledger[2]);
+        assert_eq!(game.ledger.len(), 3); // The real ledger is untouched.
+        game.undo_move();
+        game.undo_move();
+        assert_eq!(game.get_stacks()[1].top_run_len(), 2); // Undone one raw entry at a time.
+    }
+
+    #[test]
+    fn test_export_ledger_csv() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        for (from, to) in game.solve().unwrap() {
+            game.move_legally(from, to);
+        }
+        assert_eq!(game.get_move_count(), 3);
+
+        let mut csv: Vec<u8> = Vec::new();
+        game.export_ledger_csv(&mut csv).unwrap();
+        let csv: String = String::from_utf8(csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], CSV_HEADER);
+
+        let entry = &game.ledger[0];
+        let first_row: String = format!(
+            "1,{},{},{},{}",
+            entry.from + 1,
+            entry.to + 1,
+            entry.kind.get_id(),
+            entry.quantity()
+        );
+        assert_eq!(lines[1], first_row);
+    }
+
+    #[test]
+    fn test_replay_reproduces_solved_board() {
+        let start: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        let mut solved: Game = start.clone();
+        for (from, to) in start.solve().unwrap() {
+            solved.move_legally(from, to);
+        }
+
+        let mut replayed: Game = start.clone();
+        replayed.replay(solved.get_ledger()).unwrap();
+        assert!(replayed.stage_complete());
+        assert!(replayed.stacks == solved.stacks);
+        assert_eq!(replayed.get_ledger(), solved.get_ledger());
+    }
+
+    #[test]
+    fn test_replay_reports_failing_move() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        let mut entries: Vec<Entry> = game.simulate_move(0, 2).unwrap().get_ledger().to_vec();
+        let mut illegal: Entry = entries[0].clone();
+        illegal.from = 1; // Pours a 1 onto the 2 now on top of stack 3.
+        entries.push(illegal);
+
+        assert_eq!(
+            game.replay(&entries),
+            Err(ReplayError {
+                index: 1,
+                error: MoveError::TopMismatch
+            })
+        );
+        assert!(game.get_ledger().is_empty());
+    }
+
+    #[test]
+    fn test_jump_to_a_ledger_position() {
+        let start: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 1, 0], vec![2, 1, 2, 0], vec![0, 0, 0, 0]],
+            None,
+        );
+        let moves: [(usize, usize); 5] = [(0, 2), (1, 0), (1, 2), (0, 1), (2, 0)];
+        let mut game: Game = start.clone();
+        game.run_script(&moves).unwrap();
+        let end: Game = game.clone();
+
+        let mut two_moves: Game = start.clone();
+        two_moves.run_script(&moves[..2]).unwrap();
+        assert!(game.jump_to(2));
+        assert_eq!(game, two_moves);
+        assert_eq!(game.get_move_count(), 2);
+        assert_eq!(game.get_undo_count(), 3);
+
+        assert!(game.jump_to(0));
+        assert_eq!(game, start);
+        assert!(game.jump_to(5)); // The later moves weren't lost.
+        assert_eq!(game, end);
+        assert_eq!(game.get_undo_count(), 5); // Only jumping back costs undos.
+        assert!(!game.jump_to(6));
+        assert_eq!(game, end);
+    }
+
+    #[test]
+    fn test_jump_back_spends_undos() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 1, 0], vec![2, 1, 2, 0], vec![0, 0, 0, 0]],
+            None,
+        );
+        game.run_script(&[(0, 2), (1, 0), (1, 2)]).unwrap();
+        game.set_undo_limit(Some(2));
+        assert!(!game.jump_to(0)); // Three moves back, only two undos.
+        assert_eq!(game.get_move_count(), 3);
+        assert!(game.jump_to(1));
+        assert_eq!(game.undos_left(), Some(0));
+        assert!(game.jump_to(3)); // Redoing is free.
+
+        let mut shared: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 1, 0], vec![2, 1, 2, 0], vec![0, 0, 0, 0]],
+            None,
+        );
+        shared.set_two_player(true);
+        shared.run_script(&[(0, 2), (1, 0)]).unwrap();
+        assert!(!shared.jump_to(0));
+        assert_eq!(shared.get_move_count(), 2);
+    }
+}
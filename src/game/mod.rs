@@ -1,43 +1,212 @@
+mod achievements;
+mod builder;
+mod coach;
+mod config;
+mod cost;
+mod daily;
 mod entry;
+mod error;
+mod forward;
+mod game_config;
+mod generator;
 mod gui;
+mod input;
+mod keys;
+mod layout;
+mod ledger;
+mod observer;
+mod palette;
+mod players;
+mod rating;
+mod rng;
+#[cfg(feature = "serde")]
+mod save;
+mod scores;
+mod script;
+mod session;
+mod solver;
 mod stack;
+mod stage_file;
 mod stages;
+mod stats;
+mod status;
+mod style;
+mod timer;
+mod tutorial;
 
-use entry::Entry;
-use stack::kind::{HasId, IsEmpty, Kind, KindId};
-use stack::Stack;
+pub use achievements::{Achievement, Achievements, Progress, ACHIEVEMENTS};
+pub use builder::GameBuilder;
+pub use cost::CostModel;
+pub use daily::Date;
+pub use entry::Entry;
+pub use error::{
+    BuildError, ConfigError, GameError, KeyBindingError, MoveError, ReplayError, ScriptError,
+    StackParseError, StageError, StageParseError,
+};
+pub use game_config::GameConfig;
+pub use keys::KeyBindings;
+pub use observer::MoveEvent;
+pub use palette::Palette;
+pub use rating::Difficulty;
+pub use script::ScriptReport;
+pub use session::LoopOutcome;
+pub use solver::{AutoSolveOutcome, Solvability, StateKey};
+pub use stack::kind::{HasId, IsEmpty, Kind, KindId};
+pub use stack::Stack;
+pub use stage_file::StageMeta;
+pub use stats::SessionStats;
+pub use style::{EmptyStackLook, RenderStyle, Settings};
+
+use observer::Observer;
+use status::KindStatus;
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+use timer::StageTimer;
+use tutorial::Tutorial;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Game {
     stacks: Vec<Stack>,
+    #[cfg_attr(feature = "serde", serde(default))] // Filled in on load for older saves.
+    initial_stacks: Vec<Stack>, // The board the stage starts from, never moved on.
     units_per_kind: HashMap<KindId, usize>,
     kind_indices: HashMap<KindId, usize>,
-    kinds_status: usize,
+    kinds_status: KindStatus,
     turn: usize,
     stage_name: String,
     ledger: Vec<Entry>,
+    redo_ledger: Vec<Entry>,
+    par: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    undo_limit: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    undos_used: usize,
+    #[cfg_attr(feature = "serde", serde(default))]
+    undo_count: usize, // Every undo, even those a reset gave back.
+    #[cfg_attr(feature = "serde", serde(default))]
+    move_limit: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    two_player: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    current_player: usize, // Whose move it is, in two-player games.
+    #[cfg_attr(feature = "serde", serde(default))]
+    meta: StageMeta,
+    #[cfg_attr(feature = "serde", serde(default))]
+    reshuffled: bool, // No longer the stage's own board, so not scored as that stage.
+    #[cfg_attr(feature = "serde", serde(default))]
+    face_down: bool, // Boards dealt later, by a reshuffle, are hidden too.
+    #[cfg_attr(feature = "serde", serde(default))]
+    auto_solved: bool, // The solver has played moves, so the win isn't the player's own.
+    #[cfg_attr(feature = "serde", serde(default))]
+    daily: Option<Date>, // The day whose challenge this is.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    message: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    highlight: Option<(usize, usize)>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    selected: Option<usize>, // A source picked on its own, waiting for a destination.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    explicit_moves: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    show_history: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    coaching: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    optimal_distance: Option<usize>, // Kept from the last coached move, so it's solved once.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    fast_forward: bool, // Plays the forced moves after each of the player's.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    cost_model: CostModel,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    timer: StageTimer,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    quiet: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    dev: bool, // Allows skipping stages, for testing them.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    celebrated: bool, // Set once the completion has been announced.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    progress: Option<(usize, usize)>, // Stage number and count, when playing them in order.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    before_reset: Option<Arc<Game>>, // Kept until the next move, so a reset can be taken back.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    style: RenderStyle,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    empty_look: EmptyStackLook,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    compact_runs: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    palette: Arc<Palette>, // Shared, so the solver's many clones stay cheap.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    keys: Arc<KeyBindings>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    observer: Observer,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    tutorial: Option<Tutorial>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    difficulty: OnceLock<Difficulty>,
 }
 
 impl Game {
     fn new(stacks: Vec<Stack>, stage_name: Option<String>) -> Game {
         let units_per_kind: HashMap<KindId, usize> = Game::count_kinds(&stacks);
         let kind_indices: HashMap<KindId, usize> = Game::index_kinds(&units_per_kind);
-        Game {
+        let mut game: Game = Game {
+            initial_stacks: stacks.clone(),
             stacks,
             units_per_kind,
             kind_indices,
-            kinds_status: 0,
+            kinds_status: KindStatus::new(),
             turn: 1,
             stage_name: stage_name.unwrap_or("".to_string()),
             ledger: Vec::new(),
+            redo_ledger: Vec::new(),
+            par: None,
+            undo_limit: None,
+            undos_used: 0,
+            undo_count: 0,
+            move_limit: None,
+            two_player: false,
+            current_player: 0,
+            meta: StageMeta::default(),
+            reshuffled: false,
+            face_down: false,
+            auto_solved: false,
+            daily: None,
+            message: None,
+            highlight: None,
+            selected: None,
+            explicit_moves: false,
+            show_history: false,
+            coaching: false,
+            optimal_distance: None,
+            fast_forward: false,
+            cost_model: CostModel::default(),
+            timer: StageTimer::default(),
+            quiet: false,
+            dev: false,
+            celebrated: false,
+            progress: None,
+            before_reset: None,
+            style: RenderStyle::default(),
+            empty_look: EmptyStackLook::default(),
+            compact_runs: false,
+            palette: Arc::default(),
+            keys: Arc::default(),
+            observer: Observer::default(),
+            tutorial: None,
+            difficulty: OnceLock::new(),
+        };
+        for stack_ind in 0..game.stacks.len() {
+            game.update_kind_status(stack_ind); // Stacks may start out already sorted.
         }
-    }
-
-    fn clone(&self) -> Game {
-        Game::new(
-            self.stacks.iter().map(|stack| stack.clone()).collect(),
-            Some(self.stage_name.clone()),
-        )
+        game
     }
 
     fn count_kinds(stacks: &[Stack]) -> HashMap<KindId, usize> {
@@ -60,49 +229,166 @@ impl Game {
         kind_indices
     }
 
+    pub fn check_move(&self, from: usize, to: usize) -> Result<(), MoveError> {
+        if from >= self.stacks.len() || to >= self.stacks.len() {
+            return Err(MoveError::OutOfBounds);
+        }
+        if from == to {
+            return Err(MoveError::SameStack);
+        }
+        if self.is_locked(from) || self.is_locked(to) {
+            return Err(MoveError::Locked);
+        }
+        if self.stacks[from].is_vacant() {
+            return Err(MoveError::EmptySource);
+        }
+        if self.stack_tops_mismatch(from, to) {
+            return Err(MoveError::TopMismatch);
+        }
+        if self.move_requires_more_room(from, to) {
+            return Err(MoveError::NoRoom);
+        }
+        if self.move_makes_no_progress(from, to) {
+            return Err(MoveError::NoProgress);
+        }
+        Ok(())
+    }
+
+    // A lock holds until its kind is seen sorted, and comes back if that kind is split up again.
+    pub fn is_locked(&self, stack_ind: usize) -> bool {
+        match self.stacks[stack_ind].get_lock() {
+            Some(kind) => !self.shows_sorted(kind),
+            None => false,
+        }
+    }
+
+    // A kind gathered into one run, all of it face up. Face-down units of that kind under the
+    // run can't be told apart from any other, so they don't count towards it.
+    pub(crate) fn shows_sorted(&self, id: KindId) -> bool {
+        self.units_per_kind.get(&id).is_some_and(|total| {
+            self.stacks.iter().any(|stack| {
+                !stack.is_vacant() && stack.get_top_unit_id() == id && stack.top_run_len() == *total
+            })
+        })
+    }
+
     fn move_is_illegal(&self, from: usize, to: usize) -> bool {
-        (from == to) || self.move_requires_more_room(from, to) || self.stack_tops_mismatch(from, to)
+        self.check_move(from, to).is_err()
     }
 
     fn move_is_legal(&self, from: usize, to: usize) -> bool {
         !self.move_is_illegal(from, to)
     }
 
-    fn move_requires_more_room(&self, from: usize, to: usize) -> bool {
-        self.stacks[to].get_vacancy() < self.stacks[from].get_top_unit_quantity()
+    // A pour only needs room for one unit, whatever doesn't fit stays on the source.
+    fn move_requires_more_room(&self, _from: usize, to: usize) -> bool {
+        self.stacks[to].get_vacancy() == 0
+    }
+
+    // A stack of one kind poured whole into an empty stack just as tall is only moved around,
+    // and so is a sorted kind poured into a taller one. Into a shorter one it frees the taller
+    // stack, which another kind may need. Refusing a move off face-down units would tell what
+    // they are, so those are let through.
+    fn move_makes_no_progress(&self, from: usize, to: usize) -> bool {
+        let (source, target): (&Stack, &Stack) = (&self.stacks[from], &self.stacks[to]);
+        target.is_vacant()
+            && source.get_hidden() == 0
+            && source.holds_single_kind()
+            && match target.get_capacity().cmp(&source.get_capacity()) {
+                Ordering::Equal => true,
+                Ordering::Greater => source.is_complete(&self.units_per_kind),
+                Ordering::Less => false,
+            }
     }
 
     fn stack_tops_mismatch(&self, from: usize, to: usize) -> bool {
-        let immigrant_id: KindId = self.stacks[from].get_top_unit_id();
-        let resident_id: KindId = self.stacks[to].get_top_unit_id();
+        let immigrants: Kind = self.stacks[from].clone_top_unit();
+        let residents: Kind = self.stacks[to].clone_top_unit();
 
-        let tops_match: bool =
-            (immigrant_id == resident_id) || immigrant_id.is_empty() || resident_id.is_empty();
+        let tops_match: bool = (immigrants.get_id() == residents.get_id())
+            || immigrants.is_empty()
+            || residents.is_empty();
         !tops_match
     }
 
-    fn no_legal_moves(&self) -> bool {
-        for (from, _) in self.stacks.iter().enumerate() {
-            for (to, _) in self.stacks.iter().enumerate() {
+    // Empty stacks report the empty kind.
+    pub fn tops(&self) -> Vec<Kind> {
+        self.stacks
+            .iter()
+            .map(|stack| stack.clone_top_unit())
+            .collect()
+    }
+
+    // Every move check_move accepts, so moves that make no progress are left out too.
+    pub fn legal_moves(&self) -> Vec<(usize, usize)> {
+        let mut moves: Vec<(usize, usize)> = Vec::new();
+        for (from, source) in self.stacks.iter().enumerate() {
+            if source.is_vacant() {
+                continue;
+            }
+            for to in 0..self.stacks.len() {
                 if self.move_is_legal(from, to) {
-                    return false;
+                    moves.push((from, to));
                 }
             }
         }
-        true
+        moves
+    }
+
+    // The legal targets for a source, each with the number of units that would pour into it.
+    pub fn destinations(&self, from: usize) -> Vec<(usize, usize)> {
+        self.legal_moves()
+            .into_iter()
+            .filter(|(source, _)| *source == from)
+            .map(|(_, to)| (to, self.pour_quantity(from, to)))
+            .collect()
+    }
+
+    pub fn pour_quantity(&self, from: usize, to: usize) -> usize {
+        self.stacks[from]
+            .top_run_len()
+            .min(self.stacks[to].get_vacancy())
+    }
+
+    pub fn is_stuck(&self) -> bool {
+        self.legal_moves().is_empty() && !self.stage_complete()
+    }
+
+    // Every kind whose units all form a single top run, in kind order. The run may still rest
+    // on other kinds, e.g. [2, 1, 1] with three 1s.
+    pub fn completed_kinds(&self) -> Vec<Kind> {
+        self.sorted_kind_ids()
+            .into_iter()
+            .filter(|id| self.kinds_status.is_set(self.kind_indices[id]))
+            .map(|id| Kind::new(id, self.units_per_kind[&id]))
+            .collect()
+    }
+
+    pub fn sorted_kind_ids(&self) -> Vec<KindId> {
+        let mut ids: Vec<KindId> = self.kind_indices.keys().copied().collect();
+        ids.sort_by_key(|id| self.kind_indices[id]);
+        ids
+    }
+
+    // A completed stack holds its whole kind and nothing else, so that kind's status bit is
+    // always set. The two agree once the whole stage is sorted.
+    pub fn completed_stacks(&self) -> Vec<usize> {
+        (0..self.stacks.len())
+            .filter(|stack_ind| self.stacks[*stack_ind].is_complete(&self.units_per_kind))
+            .collect()
     }
 
     fn update_kind_status(&mut self, stack_ind: usize) {
-        let resident_id: KindId = self.stacks[stack_ind].get_top_unit_id();
-        if resident_id.is_empty() {
+        let residents: Kind = self.stacks[stack_ind].clone_top_unit();
+        if residents.is_empty() {
             return;
         }
-        let resident_quantity: usize = self.stacks[stack_ind].get_top_unit_quantity();
 
-        let resident_bit: usize = 1 << self.get_kind_index(resident_id);
-        self.kinds_status |= resident_bit; // Initially set the resident bit to 1.
-        if resident_quantity != self.get_total_quantity(resident_id) {
-            self.kinds_status -= resident_bit; // zero the resident bit.
+        let resident_index: usize = self.get_kind_index(residents);
+        if residents.get_quantity() == self.get_total_quantity(residents) {
+            self.kinds_status.set_bit(resident_index);
+        } else {
+            self.kinds_status.clear_bit(resident_index);
         }
     }
 
@@ -114,90 +400,506 @@ impl Game {
         self.units_per_kind[&kind_or_id.get_id()]
     }
 
-    fn ledge(&mut self, from: usize, to: usize, kind: Kind, quantity: usize) {
+    pub fn get_move_count(&self) -> usize {
+        self.ledger.len()
+    }
+
+    pub fn get_ledger(&self) -> &[Entry] {
+        &self.ledger
+    }
+
+    pub fn get_stacks(&self) -> &[Stack] {
+        &self.stacks
+    }
+
+    pub fn initial_layout(&self) -> &[Stack] {
+        &self.initial_stacks
+    }
+
+    // Puts the starting layout back and forgets every move, undone ones included.
+    pub(crate) fn restart(&mut self) {
+        self.stacks = self.initial_stacks.clone();
+        self.kinds_status = KindStatus::new();
+        for stack_ind in 0..self.stacks.len() {
+            self.update_kind_status(stack_ind);
+        }
+        self.ledger.clear();
+        self.redo_ledger.clear();
+        self.turn = 1;
+        self.current_player = 0;
+        self.difficulty = OnceLock::new();
+        self.optimal_distance = None;
+        self.before_reset = None;
+    }
+
+    pub fn total_units(&self) -> usize {
+        self.stacks.iter().map(|stack| stack.get_occupancy()).sum()
+    }
+
+    pub fn kind_count(&self) -> usize {
+        self.units_per_kind.len()
+    }
+
+    pub fn get_stage_name(&self) -> &str {
+        &self.stage_name
+    }
+
+    pub fn get_turn(&self) -> usize {
+        self.turn
+    }
+
+    fn ledge(&mut self, from: usize, to: usize, kind: Kind, source_len: usize) {
         self.ledger.push(Entry {
             from,
             to,
-            _kind: kind,
-            quantity,
+            kind,
+            source_len,
+            player: self.current_player,
         });
     }
 
     fn update_state(&mut self, from: usize, to: usize) {
         self.update_kind_status(from);
         self.update_kind_status(to);
-        self.turn += if self.stage_complete() { 0 } else { 1 };
     }
 
     fn move_units(&mut self, from: usize, to: usize, limit_: Option<usize>) {
-        let kind: Kind = self.stacks[from].pop_residents_with_limit(limit_);
-        let quantity: usize = kind.get_quantity();
-        self.stacks[to].push_immigrants(kind);
+        // Undos put a board back the way it was, locks included, so only moves are checked.
+        debug_assert!(
+            limit_.is_some() || !(self.is_locked(from) || self.is_locked(to)),
+            "moves are checked for locks before units are moved"
+        );
+        let source_len: usize = self.stacks[from].get_occupancy();
+        let kind: Kind = match limit_ {
+            Some(_) => self.stacks[from].pop_residents_with_limit(limit_),
+            None => {
+                let vacancy: usize = self.stacks[to].get_vacancy();
+                self.stacks[from].pop_residents_to_fit(vacancy)
+            }
+        };
+        self.stacks[to]
+            .push_immigrants(kind)
+            .expect("moves are checked for room before units are moved");
 
         self.update_state(from, to);
+        self.difficulty = OnceLock::new(); // Rated for the board before the move.
+        self.optimal_distance = None; // Solved for it too, a coached move solves again.
+        self.before_reset = None; // The board moved on, so the reset can't be taken back.
         match limit_ {
-            Some(_) => {} // Limits are specified in undo moves, Undo moves should not be ledged.
-            _ => self.ledge(from, to, kind, quantity),
+            Some(_) => {
+                self.turn -= 1; // Limits are specified in undo moves, Undo moves should not be ledged.
+                self.notify(MoveEvent::Undo(Entry {
+                    from,
+                    to,
+                    kind,
+                    source_len,
+                    player: self.current_player,
+                }));
+            }
+            _ => {
+                self.ledge(from, to, kind, source_len);
+                self.turn += 1;
+                self.pass_turn();
+                if let Some(entry) = self.ledger.last().cloned() {
+                    self.notify(MoveEvent::Move(entry));
+                }
+            }
         };
     }
 
-    fn move_legally(&mut self, from: usize, to: usize) {
+    pub fn try_move(&mut self, from: usize, to: usize) -> Result<(), MoveError> {
+        self.check_move(from, to)?;
+        self.redo_ledger.clear(); // A fresh move branches off, so the undone moves are gone.
         self.move_units(from, to, None);
+        Ok(())
+    }
+
+    // Illegal moves are only reported to the player, through the next prompt.
+    pub fn move_legally(&mut self, from: usize, to: usize) {
+        if let Err(error) = self.try_move(from, to) {
+            self.message = Some(Game::illegal_move_prompt(&error.to_string()));
+        }
+    }
+
+    pub fn simulate_move(&self, from: usize, to: usize) -> Option<Game> {
+        self.check_move(from, to).ok()?; // Checked first, so illegal moves aren't cloned for nothing.
+        let mut simulated: Game = self.clone();
+        simulated.try_move(from, to).ok()?;
+        Some(simulated)
     }
 
     fn move_forcefully(&mut self, from: usize, to: usize, quantity: usize) {
         self.move_units(from, to, Some(quantity));
     }
 
-    fn stage_complete(&self) -> bool {
-        self.kinds_status == (1 << self.units_per_kind.len()) - 1
+    // Reverses a move as a whole: every unit it poured goes back onto the source, which ends up
+    // as long as it was before, its remainder and the returned units merged into one run again.
+    // An entry that doesn't fit the board is refused, leaving the board as it was.
+    pub(crate) fn take_back(&mut self, entry: &Entry) -> bool {
+        if !self.can_take_back(entry) {
+            return false;
+        }
+        self.move_forcefully(entry.to, entry.from, entry.quantity());
+        true
+    }
+
+    // The poured units must still be on top of the target, with room for them on the source,
+    // which then has the length recorded before the move. Older saves didn't record it.
+    pub(crate) fn can_take_back(&self, entry: &Entry) -> bool {
+        let (Some(target), Some(source)) = (self.stacks.get(entry.to), self.stacks.get(entry.from))
+        else {
+            return false;
+        };
+        let quantity: usize = entry.quantity();
+        entry.from != entry.to
+            && quantity > 0
+            && !target.is_vacant()
+            && target.get_top_unit_id() == entry.kind.get_id()
+            && target.top_run_len() >= quantity
+            && source.get_vacancy() >= quantity
+            && (entry.source_len == 0 || source.get_occupancy() + quantity == entry.source_len)
+    }
+
+    pub fn stage_complete(&self) -> bool {
+        self.kinds_status.all_set(self.kind_count())
+    }
+
+    pub fn set_undo_limit(&mut self, undo_limit: Option<usize>) {
+        self.undo_limit = undo_limit;
+    }
+
+    pub fn set_move_limit(&mut self, move_limit: Option<usize>) {
+        self.move_limit = move_limit;
+    }
+
+    pub fn get_move_limit(&self) -> Option<usize> {
+        self.move_limit
+    }
+
+    pub fn set_meta(&mut self, meta: StageMeta) {
+        self.meta = meta;
+    }
+
+    pub fn get_meta(&self) -> &StageMeta {
+        &self.meta
+    }
+
+    pub fn get_undo_count(&self) -> usize {
+        self.undo_count
     }
 
-    fn undo_move(&mut self) {
-        match self.ledger.last() {
-            Some(entry) => {
-                let (from, to, quantity) = (entry.to, entry.from, entry.quantity);
-                self.ledger.pop();
-                self.move_forcefully(from, to, quantity);
+    // Every unit that isn't on top of its stack is turned face down, on the starting layout too
+    // so a reset hides them again.
+    pub fn hide_units(&mut self) {
+        self.face_down = true;
+        for stack in self.stacks.iter_mut().chain(self.initial_stacks.iter_mut()) {
+            stack.turn_face_down();
+        }
+    }
+
+    pub fn has_face_down_units(&self) -> bool {
+        self.stacks.iter().any(|stack| stack.get_hidden() > 0)
+    }
+
+    pub fn undos_left(&self) -> Option<usize> {
+        self.undo_limit
+            .map(|limit| limit.saturating_sub(self.undos_used))
+    }
+
+    pub fn undo_move(&mut self) {
+        if self.two_player {
+            self.message = Some("Undo is off in two-player games".to_string());
+            return;
+        }
+        if self.undos_left() == Some(0) {
+            self.message = Some("No undos left for this stage".to_string());
+            return;
+        }
+        // No moves to undo when the ledger is empty.
+        if let Some(entry) = self.ledger.pop() {
+            if !self.take_back(&entry) {
+                self.ledger.push(entry);
+                self.message =
+                    Some("The last move doesn't fit the board, so it can't be undone".to_string());
+                return;
             }
-            _ => {} // No moves to undo.
+            self.undos_used += 1;
+            self.undo_count += 1;
+            self.redo_ledger.push(entry);
         }
     }
 
-    fn turn_loop(&mut self) {
-        let stage_backup: Game = self.clone();
-        loop {
-            if self.stage_complete() {
+    // Takes back every move one at a time, so each can still be redone, unlike a reset. Stops
+    // early when undo is off or runs out, with the reason left in the message.
+    pub fn undo_all(&mut self, mut on_frame: impl FnMut(&Game)) -> usize {
+        let mut undone: usize = 0;
+        while !self.ledger.is_empty() {
+            let moves: usize = self.ledger.len();
+            self.undo_move();
+            if self.ledger.len() == moves {
                 break;
             }
-            let user_input: gui::UserInput = self.read_valid_input();
-            match user_input.stack_move {
-                Some((from, to)) => self.move_legally(from, to),
-                _ => match user_input.menu_option {
-                    gui::MenuOption::Help => self.show_help(),
-                    gui::MenuOption::Quit => std::process::exit(0),
-                    gui::MenuOption::Reset => *self = stage_backup.clone(),
-                    gui::MenuOption::Undo => self.undo_move(),
-                    _ => {}
-                },
-            }
+            undone += 1;
+            on_frame(self);
         }
+        undone
     }
 
-    pub fn play() {
-        let stages: Vec<Game> = Game::get_stages();
-        let last_stage_index: usize = stages.len() - 1;
-        for (ind, mut stage) in stages.into_iter().enumerate() {
-            stage.turn_loop();
-            stage.stage_complete_prompt(ind == last_stage_index);
+    pub fn redo_move(&mut self) {
+        // No moves to redo unless something was undone since the last move.
+        if let Some(entry) = self.redo_ledger.pop() {
+            self.current_player = entry.player; // The move is made again by whoever made it.
+            self.move_units(entry.from, entry.to, None);
         }
     }
 }
 
+// Games are equal when their boards are: the same stacks in the same order, with the same kinds
+// sorted. How the board was reached (the ledger, turn and undo history) and any view or session
+// state is left out, so a board after an undo and redo equals the one before.
+impl PartialEq for Game {
+    fn eq(&self, other: &Game) -> bool {
+        self.stacks == other.stacks
+            && self.kinds_status == other.kinds_status
+            && self.units_per_kind == other.units_per_kind
+    }
+}
+
+impl fmt::Debug for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f) // The board is what's compared, so it's what's shown.
+    }
+}
+
+// A plain dump for logs and failing tests, stacks listed bottom to top with hidden units shown.
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Stage '{}', turn {}", self.stage_name, self.turn)?;
+        for (ind, stack) in self.stacks.iter().enumerate() {
+            write!(f, "{:>3}: {}", ind + 1, stack)?;
+            if let Some(lock) = stack.get_lock() {
+                write!(
+                    f,
+                    " (locked until {})",
+                    Kind::char_from_id(lock).unwrap_or('?')
+                )?;
+            }
+            writeln!(f)?;
+        }
+        let sorted: Vec<String> = self
+            .sorted_kind_ids()
+            .into_iter()
+            .filter(|id| self.kinds_status.is_set(self.kind_indices[id]))
+            .map(|id| Kind::char_from_id(id).unwrap_or('?').to_string())
+            .collect();
+        write!(
+            f,
+            "Sorted kinds: {} of {} [{}]",
+            sorted.len(),
+            self.kind_count(),
+            sorted.join(" ")
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_face_down_units_are_revealed_by_moves() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        game.hide_units();
+        assert!(game.has_face_down_units());
+        assert_eq!(
+            game.render_to_string_with_width(RenderStyle::Ascii, 40)
+                .matches('?')
+                .count(),
+            2
+        );
+
+        game.move_legally(0, 2);
+        assert_eq!(game.stacks[0].get_hidden(), 0);
+        assert_eq!(game.stacks[0].get_top_unit_id(), 1); // Revealed once on top.
+        game.move_legally(1, 0);
+        assert!(!game.has_face_down_units());
+    }
+
+    #[test]
+    fn test_display_for_debugging() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![2, 0, 0], vec![2, 1, 0], vec![1, 0, 0]],
+            Some("Debug".to_string()),
+        );
+        game.move_legally(1, 2);
+        let shown: String = game.to_string();
+        assert!(shown.starts_with("Stage 'Debug', turn 2\n"));
+        assert!(shown.contains("  1: B..\n"));
+        assert!(shown.contains("  2: B..\n"));
+        assert!(shown.contains("  3: AA.\n"));
+        assert!(shown.ends_with("Sorted kinds: 1 of 2 [A]"));
+    }
+
+    #[test]
+    fn test_games_compare_by_board() {
+        let vecs: Vec<Vec<usize>> = vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]];
+        let game: Game = Game::new_from_vecs(vecs.clone(), Some("One".to_string()));
+        assert_eq!(
+            game,
+            Game::new_from_vecs(vecs.clone(), Some("Two".to_string()))
+        );
+
+        let mut moved: Game = game.clone();
+        moved.move_legally(0, 2);
+        assert_ne!(moved, game);
+        moved.undo_move();
+        moved.redo_move();
+        moved.undo_move();
+        assert_eq!(moved, game); // Only the redo history tells them apart.
+
+        let reordered: Game =
+            Game::new_from_vecs(vec![vec![2, 1, 0], vec![1, 2, 0], vec![0, 0, 0]], None);
+        assert_ne!(reordered, game);
+    }
+
+    #[test]
+    fn test_undo_all_returns_to_the_start() {
+        let start: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        let mut game: Game = start.clone();
+        game.run_script(&[(0, 2), (1, 0), (1, 2)]).unwrap();
+        let end: Game = game.clone();
+
+        let mut frames: usize = 0;
+        assert_eq!(game.undo_all(|_| frames += 1), 3);
+        assert_eq!(frames, 3);
+        assert!(game.get_ledger().is_empty());
+        assert_eq!(game, start);
+
+        for _ in 0..3 {
+            game.redo_move(); // Unlike a reset, every move can be redone.
+        }
+        assert_eq!(game, end);
+
+        game.undo_limit = Some(4); // Three were used already, one is left.
+        assert_eq!(game.undo_all(|_| {}), 1);
+        assert_eq!(game.get_move_count(), 2);
+    }
+
+    #[test]
+    fn test_initial_layout_survives_moves() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        let start: Vec<Stack> = game.stacks.clone();
+        game.run_script(&[(0, 2), (1, 0), (1, 2)]).unwrap();
+        assert!(game.stage_complete());
+        assert!(game.initial_layout() == start.as_slice());
+
+        game.restart();
+        assert!(game.stacks == game.initial_stacks);
+        assert!(game.get_ledger().is_empty());
+        assert!(!game.stage_complete());
+    }
+
+    #[test]
+    fn test_total_units_and_kind_count() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 1, 0], vec![3, 2, 0, 0], vec![1, 0, 0, 0]],
+            None,
+        );
+        assert_eq!(game.total_units(), 6);
+        assert_eq!(game.kind_count(), 3);
+
+        game.move_legally(0, 2); // Moves don't change either.
+        assert_eq!(game.get_move_count(), 1);
+        assert_eq!(game.total_units(), 6);
+        assert_eq!(game.kind_count(), 3);
+    }
+
+    #[test]
+    fn test_locked_stack_opens_once_its_kind_is_sorted() {
+        let mut stacks: Vec<Stack> = vec![
+            Stack::new_from_vec(vec![2, 1, 0]),
+            Stack::new_from_vec(vec![1, 0, 0]),
+            Stack::new_from_vec(vec![0, 0, 0]),
+            Stack::new_from_vec(vec![2, 3, 0]),
+        ];
+        stacks[2].lock_until(1);
+        let mut game: Game = Game::new(stacks, None);
+        assert_eq!(game.check_move(0, 2), Err(MoveError::Locked));
+        assert!(!game.legal_moves().iter().any(|(_, to)| *to == 2));
+
+        game.move_legally(0, 1); // Gathers both 1s, which opens the lock.
+        assert!(!game.is_locked(2));
+        assert_eq!(game.check_move(3, 2), Ok(()));
+
+        game.undo_move(); // Splitting the 1s up again closes it.
+        assert!(game.is_locked(2));
+    }
+
+    #[test]
+    fn test_lock_waits_for_the_sorted_kind_to_show() {
+        let mut stacks: Vec<Stack> = vec![
+            Stack::new_from_vec(vec![1, 1, 0]),
+            Stack::new_from_vec(vec![2, 3, 0]),
+            Stack::new_from_vec(vec![0, 0, 0]),
+            Stack::new_from_vec(vec![3, 2, 0]),
+            Stack::new_from_vec(vec![0, 0, 0]),
+        ];
+        stacks[2].lock_until(1);
+        let mut game: Game = Game::new(stacks, None);
+        assert!(!game.is_locked(2));
+        game.hide_units();
+        assert!(game.is_locked(2)); // Only one of the 1s shows.
+
+        game.move_legally(0, 4); // Turns the other 1 face up.
+        assert!(game.is_locked(2));
+        game.move_legally(4, 0);
+        assert!(!game.is_locked(2));
+    }
+
+    #[test]
+    fn test_completed_kinds() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 0], vec![2, 1, 0], vec![3, 3, 0], vec![0, 0, 0]],
+            None,
+        );
+        assert_eq!(game.completed_kinds(), vec![Kind::new(3, 2)]);
+        game.move_legally(0, 3);
+        game.move_legally(1, 0);
+        assert_eq!(
+            game.completed_kinds(),
+            vec![Kind::new(1, 2), Kind::new(3, 2)]
+        );
+        game.move_legally(3, 1);
+        assert_eq!(
+            game.completed_kinds(),
+            vec![Kind::new(1, 2), Kind::new(2, 2), Kind::new(3, 2)]
+        );
+    }
+
+    #[test]
+    fn test_undo_limit() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 0, 0], vec![2, 1, 0, 0], vec![0, 0, 0, 0]],
+            None,
+        );
+        game.set_undo_limit(Some(1));
+        game.move_legally(0, 2);
+        game.move_legally(1, 0);
+        assert_eq!(game.undos_left(), Some(1));
+        game.undo_move();
+        assert_eq!(game.undos_left(), Some(0));
+
+        let ledger: Vec<Entry> = game.ledger.clone();
+        let stacks: Vec<Stack> = game.stacks.clone();
+        game.undo_move();
+        assert_eq!(game.ledger, ledger);
+        assert!(game.stacks == stacks);
+        assert!(game.message.is_some());
+    }
+
     #[test]
     fn test_get_stages() {
         let stages: Vec<Game> = Game::get_stages();
@@ -207,4 +909,345 @@ mod tests {
         let mut last_stage = stages[last_stage_index].clone();
         last_stage.move_legally(0, 1);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_undo_then_redo_restores_board() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        game.move_legally(0, 2);
+        game.move_legally(1, 0);
+        let stacks_after_moves: Vec<Stack> = game.stacks.clone();
+        let status_after_moves: KindStatus = game.kinds_status.clone();
+
+        game.undo_move();
+        game.undo_move();
+        assert_eq!(game.redo_ledger.len(), 2);
+        game.redo_move();
+        game.redo_move();
+
+        assert!(game.stacks == stacks_after_moves);
+        assert_eq!(game.kinds_status, status_after_moves);
+        assert_eq!(game.ledger.len(), 2);
+        assert!(game.redo_ledger.is_empty());
+    }
+
+    #[test]
+    fn test_clone_keeps_progress() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        game.move_legally(0, 2);
+        game.move_legally(1, 0);
+        game.move_legally(1, 2);
+        let copy: Game = game.clone();
+
+        assert_eq!(copy.ledger.len(), 3);
+        assert_eq!(copy.ledger, game.ledger);
+        assert_eq!(copy.turn, game.turn);
+        assert_eq!(copy.kinds_status, game.kinds_status);
+        assert!(copy.stacks == game.stacks);
+    }
+
+    #[test]
+    fn test_illegal_move_reports_reason() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![2, 1, 1], vec![2, 1, 1, 0], vec![2, 0, 0]], None);
+        assert_eq!(game.check_move(0, 2), Err(MoveError::TopMismatch));
+        assert_eq!(game.check_move(1, 0), Err(MoveError::NoRoom));
+        assert_eq!(game.check_move(1, 1), Err(MoveError::SameStack));
+
+        game.move_legally(1, 0);
+        assert!(game.ledger.is_empty());
+        assert!(game.message.unwrap().contains("full"));
+    }
+
+    #[test]
+    fn test_try_move_errors() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![2, 1, 1], vec![2, 1, 0, 0], vec![2, 0, 0], vec![0, 0]],
+            None,
+        );
+        assert_eq!(game.try_move(0, 4), Err(MoveError::OutOfBounds));
+        assert_eq!(game.try_move(4, 0), Err(MoveError::OutOfBounds));
+        assert_eq!(game.try_move(1, 1), Err(MoveError::SameStack));
+        assert_eq!(game.try_move(3, 0), Err(MoveError::EmptySource));
+        assert_eq!(game.try_move(0, 2), Err(MoveError::TopMismatch));
+        assert_eq!(game.try_move(1, 0), Err(MoveError::NoRoom));
+        assert!(game.ledger.is_empty());
+        assert_eq!(game.message, None);
+
+        assert_eq!(game.try_move(0, 1), Ok(()));
+        assert_eq!(game.get_move_count(), 1);
+    }
+
+    #[test]
+    fn test_sorted_kind_is_not_moved_around() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![
+                vec![3, 3, 0],
+                vec![1, 2, 0],
+                vec![1, 2, 0],
+                vec![0, 0, 0],
+                vec![0, 0],
+            ],
+            None,
+        );
+        assert_eq!(game.try_move(0, 3), Err(MoveError::NoProgress));
+        assert!(game.ledger.is_empty());
+        assert!(!game.legal_moves().contains(&(0, 3)));
+        assert_eq!(game.check_move(0, 4), Ok(())); // Frees the taller stack.
+
+        assert_eq!(game.try_move(1, 2), Ok(())); // Gathers the 2s.
+        assert_eq!(game.try_move(1, 3), Err(MoveError::NoProgress)); // The lone 1 just moves.
+        assert_eq!(game.try_move(1, 4), Ok(()));
+
+        let mut hidden: Game = Game::new_from_vecs(
+            vec![vec![3, 3, 0], vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]],
+            None,
+        );
+        hidden.hide_units();
+        assert_eq!(hidden.check_move(0, 3), Ok(())); // Refusing would show the 3s are sorted.
+        assert!(hidden.legal_moves().contains(&(0, 3)));
+    }
+
+    #[test]
+    fn test_tops_include_empty_stacks() {
+        let game: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 2], vec![0, 0], vec![3, 1, 0], vec![2, 0, 0, 0]],
+            None,
+        );
+        assert_eq!(
+            game.tops(),
+            vec![
+                Kind::new(2, 2),
+                Kind::new_empty(),
+                Kind::new(1, 1),
+                Kind::new(2, 1)
+            ]
+        );
+        assert!(game.tops()[1].is_empty());
+    }
+
+    #[test]
+    fn test_completed_stacks() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![
+                vec![1, 1, 1],
+                vec![3, 2, 2, 0],
+                vec![2, 0, 0],
+                vec![3, 0, 0],
+            ],
+            None,
+        );
+        assert_eq!(game.completed_stacks(), vec![0]);
+        assert!(game.kinds_status.is_set(game.get_kind_index(1)));
+        assert!(!game.kinds_status.is_set(game.get_kind_index(2)));
+
+        game.move_legally(2, 1); // All three 2s now top stack 2, still resting on a 3.
+        assert!(game.kinds_status.is_set(game.get_kind_index(2)));
+        assert_eq!(game.completed_stacks(), vec![0]);
+    }
+
+    #[test]
+    fn test_partial_pour_leaves_remainder() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![2, 2, 1, 1], vec![1, 1, 0], vec![0, 0, 0, 0]],
+            None,
+        );
+        game.move_legally(0, 1);
+        assert_eq!(
+            game.stacks[0].iter_unit_ids().collect::<Vec<_>>(),
+            vec![2, 2, 1]
+        );
+        assert_eq!(game.stacks[1].get_vacancy(), 0);
+        assert_eq!(game.ledger[0].quantity(), 1);
+        assert!(!game.kinds_status.is_set(game.get_kind_index(1)));
+        assert!(!game.kinds_status.is_set(game.get_kind_index(2)));
+
+        game.move_legally(1, 2);
+        game.move_legally(0, 2);
+        assert!(game.kinds_status.is_set(game.get_kind_index(1)));
+        assert!(game.kinds_status.is_set(game.get_kind_index(2)));
+        assert!(game.stage_complete());
+
+        game.undo_move();
+        game.undo_move();
+        game.undo_move();
+        assert!(
+            game.stacks
+                == Game::new_from_vecs(
+                    vec![vec![2, 2, 1, 1], vec![1, 1, 0], vec![0, 0, 0, 0]],
+                    None,
+                )
+                .stacks
+        );
+    }
+
+    #[test]
+    fn test_undo_takes_back_a_partial_pour_exactly() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![2, 1, 1, 1], vec![1, 0, 0], vec![2, 0, 0, 0]],
+            None,
+        );
+        let before: Vec<Stack> = game.stacks.clone();
+        game.move_legally(0, 1); // Only two of the three 1s fit.
+        let entry: &Entry = &game.ledger[0];
+        assert_eq!(
+            (entry.kind, entry.quantity(), entry.source_len),
+            (Kind::new(1, 2), 2, 4)
+        );
+        assert_eq!(
+            game.stacks[0].get_units(),
+            &[Kind::new(2, 1), Kind::new(1, 1)]
+        );
+
+        // A record that doesn't put the source back to its length is refused, not half undone.
+        game.ledger[0].source_len = 3;
+        game.undo_move();
+        assert_eq!(game.get_move_count(), 1);
+        assert_eq!(game.undos_used, 0);
+        assert!(game.message.take().unwrap().contains("can't be undone"));
+
+        game.ledger[0].source_len = 4;
+        game.undo_move();
+        assert!(game.stacks == before);
+        assert_eq!(
+            game.stacks[0].get_units(),
+            &[Kind::new(2, 1), Kind::new(1, 3)]
+        );
+        assert_eq!(game.stacks[1].get_units(), &[Kind::new(1, 1)]);
+    }
+
+    #[test]
+    fn test_move_lifts_only_top_run() {
+        let mut game: Game = Game::new_from_vecs(vec![vec![1, 1, 2, 2], vec![0, 0, 0, 0]], None);
+        game.move_legally(0, 1);
+        assert_eq!(
+            game.stacks[0].iter_unit_ids().collect::<Vec<_>>(),
+            vec![1, 1]
+        );
+        assert_eq!(
+            game.stacks[1].iter_unit_ids().collect::<Vec<_>>(),
+            vec![2, 2]
+        );
+        assert_eq!(game.ledger[0].quantity(), 2);
+    }
+
+    #[test]
+    fn test_out_of_range_move_is_rejected() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        let stack_count: usize = game.stacks.len();
+        assert_eq!(game.check_move(stack_count, 0), Err(MoveError::OutOfBounds));
+        assert_eq!(game.check_move(0, stack_count), Err(MoveError::OutOfBounds));
+
+        game.move_legally(stack_count, 0);
+        game.move_legally(0, stack_count);
+        game.move_legally(0, 0);
+        assert!(game.ledger.is_empty());
+        assert_eq!(game.turn, 1);
+        assert!(game.message.is_some());
+    }
+
+    #[test]
+    fn test_completion_with_many_kinds() {
+        let mut vecs: Vec<Vec<usize>> = (1..=70).map(|kind| vec![kind, kind]).collect();
+        vecs[69] = vec![70, 0];
+        vecs.push(vec![70, 0]);
+        let mut game: Game = Game::new_from_vecs(vecs, None);
+        assert!(!game.stage_complete());
+
+        game.move_legally(70, 69);
+        assert!(game.stage_complete());
+        game.undo_move();
+        assert!(!game.stage_complete());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        game.move_legally(0, 2);
+        game.move_legally(1, 0);
+
+        let json: String = serde_json::to_string(&game).unwrap();
+        let restored: Game = serde_json::from_str(&json).unwrap();
+        assert!(restored.stacks == game.stacks);
+        assert_eq!(restored.ledger, game.ledger);
+        assert_eq!(restored.turn, game.turn);
+        assert_eq!(restored.kinds_status, game.kinds_status);
+        assert_eq!(restored.units_per_kind, game.units_per_kind);
+    }
+
+    #[test]
+    fn test_simulate_move_leaves_game_untouched() {
+        let game: Game = Game::new_from_vecs(vec![vec![1, 2, 0], vec![1, 2, 0, 0]], None);
+        let simulated: Game = game.simulate_move(0, 1).unwrap();
+
+        assert_eq!(game.stacks[0].get_occupancy(), 2);
+        assert_eq!(game.stacks[1].get_top_unit_quantity(), 1);
+        assert!(game.ledger.is_empty());
+        assert!(!game.kinds_status.is_set(game.get_kind_index(2)));
+
+        assert_eq!(simulated.stacks[0].get_occupancy(), 1);
+        assert_eq!(simulated.stacks[1].get_top_unit_quantity(), 2);
+        assert_eq!(simulated.ledger.len(), 1);
+        assert!(simulated.kinds_status.is_set(game.get_kind_index(2)));
+        assert!(game.simulate_move(1, 1).is_none());
+    }
+
+    #[test]
+    fn test_legal_moves() {
+        let game: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 0], vec![2, 0, 0], vec![0, 0, 0], vec![1, 1, 0]],
+            None,
+        );
+        // Stack 2 and stack 4 hold a single kind, so pouring them into stack 3 gains nothing.
+        assert_eq!(game.legal_moves(), vec![(0, 1), (0, 2), (1, 0)]);
+    }
+
+    #[test]
+    fn test_is_stuck() {
+        let stuck: Game = Game::new_from_vecs(vec![vec![1, 2], vec![2, 1]], None);
+        assert!(stuck.is_stuck());
+
+        let mut in_progress: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        assert!(!in_progress.is_stuck());
+        for (from, to) in in_progress.solve().unwrap() {
+            in_progress.move_legally(from, to);
+        }
+        assert!(in_progress.stage_complete());
+        assert!(!in_progress.is_stuck());
+    }
+
+    #[test]
+    fn test_undo_rewinds_turn() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0], vec![0, 0, 0]],
+            None,
+        );
+        game.move_legally(0, 2);
+        game.move_legally(1, 3);
+        game.move_legally(0, 3);
+        assert_eq!(game.turn, 4);
+        assert_eq!(game.get_move_count(), 3);
+
+        game.undo_move();
+        game.undo_move();
+        assert_eq!(game.turn, 2);
+        assert_eq!(game.get_move_count(), 1);
+    }
+
+    #[test]
+    fn test_new_move_clears_redo() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        game.move_legally(0, 2);
+        game.undo_move();
+        game.move_legally(1, 2);
+        assert!(game.redo_ledger.is_empty());
+        game.redo_move();
+        assert_eq!(game.ledger.len(), 1);
+    }
+}
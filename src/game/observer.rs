@@ -0,0 +1,96 @@
+use crate::game::entry::Entry;
+use crate::game::Game;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum MoveEvent {
+    Move(Entry),
+    Undo(Entry), // The units that went back, from where they were to where they came from.
+}
+
+// Copies of a game are look-aheads (the solver, simulated moves), so they never report back.
+// Send and Sync, so a game can still be shared with other threads.
+type Callback = Box<dyn FnMut(&MoveEvent) + Send + Sync>;
+
+#[derive(Default)]
+pub struct Observer(Option<Callback>);
+
+impl Clone for Observer {
+    fn clone(&self) -> Observer {
+        Observer(None)
+    }
+}
+
+impl Game {
+    pub fn set_observer(&mut self, callback: impl FnMut(&MoveEvent) + Send + Sync + 'static) {
+        self.observer = Observer(Some(Box::new(callback)));
+    }
+
+    pub fn clear_observer(&mut self) {
+        self.observer = Observer(None);
+    }
+
+    pub(crate) fn notify(&mut self, event: MoveEvent) {
+        if let Some(callback) = self.observer.0.as_mut() {
+            callback(&event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_observer_fires_once_per_move() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 0, 0], vec![2, 1, 0, 0], vec![0, 0, 0, 0]],
+            None,
+        );
+        let events: Arc<Mutex<Vec<MoveEvent>>> = Arc::new(Mutex::new(Vec::new()));
+        let seen: Arc<Mutex<Vec<MoveEvent>>> = events.clone();
+        game.set_observer(move |event| seen.lock().unwrap().push(event.clone()));
+
+        game.move_legally(0, 2);
+        game.move_legally(0, 0); // Illegal, so nothing is reported.
+        game.move_legally(1, 0);
+        let _ = game.simulate_move(1, 2); // Look-aheads stay silent.
+        assert_eq!(events.lock().unwrap().len(), 2);
+
+        game.undo_move();
+        let events: Vec<MoveEvent> = events.lock().unwrap().clone();
+        assert_eq!(events.len(), 3);
+        assert!(matches!(&events[0], MoveEvent::Move(entry) if entry.from == 0));
+        match &events[2] {
+            MoveEvent::Undo(entry) => {
+                assert_eq!((entry.from, entry.to, entry.quantity()), (0, 1, 1))
+            }
+            event => panic!("expected an undo, got {:?}", event),
+        }
+    }
+
+    #[test]
+    fn test_observer_hears_replayed_moves_and_stays() {
+        let start: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 0, 0], vec![2, 1, 0, 0], vec![0, 0, 0, 0]],
+            None,
+        );
+        let mut played: Game = start.clone();
+        played.run_script(&[(0, 2), (1, 0)]).unwrap();
+
+        let mut game: Game = start.clone();
+        let events: Arc<Mutex<Vec<MoveEvent>>> = Arc::new(Mutex::new(Vec::new()));
+        let seen: Arc<Mutex<Vec<MoveEvent>>> = events.clone();
+        game.set_observer(move |event| seen.lock().unwrap().push(event.clone()));
+        let mut broken: Vec<Entry> = played.get_ledger().to_vec();
+        broken.push(broken[0].clone()); // Stack 1 is empty by then.
+        assert!(game.replay(&broken).is_err());
+        assert!(events.lock().unwrap().is_empty()); // Nothing was applied.
+
+        game.replay(played.get_ledger()).unwrap();
+        assert_eq!(events.lock().unwrap().len(), 2);
+        assert!(matches!(&events.lock().unwrap()[1], MoveEvent::Move(entry) if entry.from == 1));
+        game.undo_move();
+        assert_eq!(events.lock().unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::game::config;
+use crate::game::stack::kind::Kind;
+use std::collections::HashMap;
+use std::fs;
+
+// Colors and symbols for each kind, looked up by the kind's index among the stage's sorted ids.
+const STANDARD_COLORS: [[u8; 3]; 11] = [
+    [255, 0, 0],
+    [0, 255, 0],
+    [0, 0, 255],
+    [255, 255, 0],
+    [0, 255, 255],
+    [255, 0, 255],
+    // [127, 255, 0],
+    // [0, 127, 255],
+    // [255, 0, 127],
+    [255, 127, 0],
+    [0, 255, 127],
+    // [127, 0, 255],
+    [255, 127, 127],
+    // [127, 255, 127],
+    // [127, 127, 255],
+    [127, 127, 127],
+    [255, 255, 255],
+    // [0, 0, 0],
+];
+
+// The Okabe-Ito set, told apart under the common kinds of color blindness.
+const COLORBLIND_COLORS: [[u8; 3]; 8] = [
+    [230, 159, 0],
+    [86, 180, 233],
+    [0, 158, 115],
+    [240, 228, 66],
+    [0, 114, 178],
+    [213, 94, 0],
+    [204, 121, 167],
+    [255, 255, 255],
+];
+
+const NAMED_COLORS: [(&str, [u8; 3]); 13] = [
+    ("red", [255, 0, 0]),
+    ("green", [0, 255, 0]),
+    ("blue", [0, 0, 255]),
+    ("yellow", [255, 255, 0]),
+    ("cyan", [0, 255, 255]),
+    ("magenta", [255, 0, 255]),
+    ("orange", [255, 127, 0]),
+    ("pink", [255, 127, 127]),
+    ("purple", [127, 0, 255]),
+    ("brown", [150, 75, 0]),
+    ("gray", [127, 127, 127]),
+    ("grey", [127, 127, 127]),
+    ("white", [255, 255, 255]),
+];
+
+const SYMBOLS: [char; 16] = [
+    '●', '▲', '■', '◆', '★', '♥', '♣', '♠', '✚', '✖', '☀', '☂', '♪', '☘', '⬟', '⬢',
+];
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Palette {
+    colors: Vec<[u8; 3]>,
+    overrides: HashMap<usize, [u8; 3]>,
+    symbols: bool,
+}
+
+impl Default for Palette {
+    fn default() -> Palette {
+        Palette::standard()
+    }
+}
+
+impl Palette {
+    pub fn standard() -> Palette {
+        Palette {
+            colors: STANDARD_COLORS.to_vec(),
+            overrides: HashMap::new(),
+            symbols: false,
+        }
+    }
+
+    // Safe colors, with a symbol drawn on every cell so color is never needed on its own.
+    pub fn colorblind() -> Palette {
+        Palette {
+            colors: COLORBLIND_COLORS.to_vec(),
+            overrides: HashMap::new(),
+            symbols: true,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Palette> {
+        match name {
+            "standard" => Some(Palette::standard()),
+            "colorblind" => Some(Palette::colorblind()),
+            _ => None,
+        }
+    }
+
+    // Applies the user's palette file on top of this palette, if there is one.
+    pub fn with_user_config(mut self) -> Palette {
+        let Some(path) = config::palette_path().filter(|path| path.is_file()) else {
+            return self;
+        };
+        match fs::read_to_string(&path) {
+            Ok(text) => {
+                for warning in self.apply_config(&text) {
+                    eprintln!("{}: {}", path.display(), warning);
+                }
+            }
+            Err(error) => eprintln!("Could not read {}: {}", path.display(), error),
+        }
+        self
+    }
+
+    // Each line maps a kind index to a color, e.g. `0 = #ff8800`, `1 = 0, 128, 255` or `2 = orange`.
+    // Blank lines and `#` comments are skipped. A bad line keeps the default color for that kind
+    // and is returned as a warning instead.
+    pub fn apply_config(&mut self, text: &str) -> Vec<String> {
+        let mut warnings: Vec<String> = Vec::new();
+        for (line_index, line) in text.lines().enumerate() {
+            let line: &str = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parsed: Option<(usize, [u8; 3])> = line.split_once('=').and_then(|(key, value)| {
+                Some((
+                    key.trim().parse::<usize>().ok()?,
+                    parse_color(value.trim())?,
+                ))
+            });
+            match parsed {
+                Some((kind_index, color)) => {
+                    self.overrides.insert(kind_index, color);
+                }
+                None => warnings.push(format!(
+                    "line {}: ignoring '{}', expected '<kind index> = <color>'",
+                    line_index + 1,
+                    line
+                )),
+            }
+        }
+        warnings
+    }
+
+    pub fn color(&self, kind_index: usize) -> [u8; 3] {
+        match self.overrides.get(&kind_index) {
+            Some(color) => *color,
+            None => self.colors[kind_index % self.colors.len()],
+        }
+    }
+
+    // Past the symbol set, kinds fall back to letters, so no two kinds ever share a symbol.
+    pub fn symbol(&self, kind_index: usize) -> Option<char> {
+        if !self.symbols {
+            return None;
+        }
+        match SYMBOLS.get(kind_index) {
+            Some(symbol) => Some(*symbol),
+            None => Some(Kind::char_from_id(kind_index - SYMBOLS.len() + 1).unwrap_or('?')),
+        }
+    }
+}
+
+fn parse_color(text: &str) -> Option<[u8; 3]> {
+    if let Some(hex) = text.strip_prefix('#') {
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |ind: usize| u8::from_str_radix(&hex[ind..ind + 2], 16).ok();
+        return Some([channel(0)?, channel(2)?, channel(4)?]);
+    }
+    let channels: Vec<&str> = text.split(',').map(|channel| channel.trim()).collect();
+    if channels.len() == 3 {
+        let mut color: [u8; 3] = [0; 3];
+        for (slot, channel) in color.iter_mut().zip(channels) {
+            *slot = channel.parse::<u8>().ok()?;
+        }
+        return Some(color);
+    }
+    let name: String = text.to_ascii_lowercase();
+    NAMED_COLORS
+        .iter()
+        .find(|(color_name, _)| *color_name == name)
+        .map(|(_, color)| *color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Game;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_every_kind_gets_a_unique_symbol() {
+        let palette: Palette = Palette::colorblind();
+        let many_kinds: Game = Game::new_from_vecs((1..=60).map(|id| vec![id]).collect(), None);
+        for stage in Game::get_stages().iter().chain([&many_kinds]) {
+            let mut symbols: HashSet<char> = HashSet::new();
+            for (kind, index) in &stage.kind_indices {
+                let symbol: char = palette.symbol(*index).unwrap();
+                assert!(symbols.insert(symbol), "kind {} repeats {}", kind, symbol);
+            }
+        }
+        assert_eq!(Palette::standard().symbol(0), None);
+    }
+
+    #[test]
+    fn test_custom_palette_colors_the_right_kind() {
+        let mut palette: Palette = Palette::standard();
+        let warnings: Vec<String> = palette
+            .apply_config("# custom\n1 = #102030\n2 = 1, 2, 3\n3 = pink\n0 = #zzzzzz\nsky\n");
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].starts_with("line 5:"));
+        assert_eq!(palette.color(0), STANDARD_COLORS[0]); // The bad entry keeps the default.
+        assert_eq!(palette.color(1), [16, 32, 48]);
+        assert_eq!(palette.color(2), [1, 2, 3]);
+        assert_eq!(palette.color(3), [255, 127, 127]);
+        assert_eq!(palette.color(4), STANDARD_COLORS[4]);
+
+        let mut game: Game = Game::new_from_vecs(vec![vec![3, 7, 0], vec![7, 3, 0]], None);
+        game.palette = std::sync::Arc::new(palette);
+        let frame: String = game.render_to_string_with_width(crate::game::RenderStyle::Color, 80);
+        // Kind 7 sorts second, so it takes the color set for index 1.
+        assert!(frame.contains("\x1b[38;2;16;32;48m 7"));
+        assert!(!frame.contains("\x1b[38;2;16;32;48m 3"));
+    }
+}
@@ -0,0 +1,79 @@
+use crate::game::Game;
+
+// Two players share the board and alternate moves. Undo is off, so neither can take back the
+// other's moves.
+impl Game {
+    pub fn set_two_player(&mut self, two_player: bool) {
+        self.two_player = two_player;
+        self.current_player = 0;
+    }
+
+    pub fn is_two_player(&self) -> bool {
+        self.two_player
+    }
+
+    // Numbered from 0.
+    pub fn get_current_player(&self) -> usize {
+        self.current_player
+    }
+
+    pub(crate) fn pass_turn(&mut self) {
+        if self.two_player {
+            self.current_player = 1 - self.current_player;
+        }
+    }
+
+    // Whoever made the move that sorted the last kind.
+    pub fn winner(&self) -> Option<usize> {
+        match self.two_player && self.stage_complete() {
+            true => self.ledger.last().map(|entry| entry.player),
+            false => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completing_player_wins() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 0, 0], vec![2, 1, 0, 0], vec![0, 0, 0, 0]],
+            None,
+        );
+        game.set_two_player(true);
+        let solution: Vec<(usize, usize)> = game.solve().unwrap();
+        for (ind, (from, to)) in solution.iter().enumerate() {
+            assert_eq!(game.get_current_player(), ind % 2);
+            assert_eq!(game.winner(), None);
+            game.move_legally(*from, *to);
+        }
+        assert!(game.stage_complete());
+        assert_eq!(game.winner(), Some((solution.len() - 1) % 2));
+        assert!(game
+            .ledger
+            .iter()
+            .enumerate()
+            .all(|(ind, entry)| entry.player == ind % 2));
+
+        game.undo_move(); // Nobody can take the win back.
+        assert_eq!(game.winner(), Some((solution.len() - 1) % 2));
+    }
+
+    #[test]
+    fn test_redo_keeps_the_player_who_moved() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 0, 0], vec![2, 1, 0, 0], vec![0, 0, 0, 0]],
+            None,
+        );
+        game.set_two_player(true);
+        game.run_script(&[(0, 2), (1, 0)]).unwrap();
+        game.two_player = false; // Undo is off for two players, so the move is taken back alone.
+        game.undo_move();
+        game.two_player = true;
+        game.redo_move();
+        assert_eq!(game.ledger[1].player, 1);
+        assert_eq!(game.get_current_player(), 0);
+    }
+}
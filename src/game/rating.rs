@@ -0,0 +1,154 @@
+use crate::game::style::RenderStyle;
+use crate::game::Game;
+
+pub const MAX_STARS: usize = 3;
+// Minimum efficiency (par / moves, in percent) needed for each star beyond the first.
+pub const THREE_STAR_EFFICIENCY: usize = 100;
+pub const TWO_STAR_EFFICIENCY: usize = 75;
+// Highest difficulty score of each rating, the score being the optimal number of moves times
+// the average number of legal moves on the way (so long stages with many tempting moves rate
+// hard). Stages the solver gives up on rate Expert.
+pub const EASY_SCORE: usize = 10;
+pub const MEDIUM_SCORE: usize = 60;
+pub const HARD_SCORE: usize = 200;
+const DIFFICULTY_SEARCH_LIMIT: usize = 50_000;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+    Expert,
+}
+
+impl Difficulty {
+    pub fn from_score(score: usize) -> Difficulty {
+        match score {
+            score if score <= EASY_SCORE => Difficulty::Easy,
+            score if score <= MEDIUM_SCORE => Difficulty::Medium,
+            score if score <= HARD_SCORE => Difficulty::Hard,
+            _ => Difficulty::Expert,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Medium => "Medium",
+            Difficulty::Hard => "Hard",
+            Difficulty::Expert => "Expert",
+        }
+    }
+}
+
+impl Game {
+    // Solving is expensive, so the rating is worked out once and kept until the next move.
+    pub fn difficulty(&self) -> Difficulty {
+        *self.difficulty.get_or_init(|| self.rate_difficulty())
+    }
+
+    // Without working it out, for when there's no time to solve the stage.
+    pub fn cached_difficulty(&self) -> Option<Difficulty> {
+        self.difficulty.get().copied()
+    }
+
+    fn rate_difficulty(&self) -> Difficulty {
+        let Some(solution) = self.solve_within(DIFFICULTY_SEARCH_LIMIT) else {
+            return Difficulty::Expert;
+        };
+        if solution.is_empty() {
+            return Difficulty::Easy;
+        }
+        let mut board: Game = self.clone();
+        let mut branching: usize = 0;
+        for (from, to) in &solution {
+            branching += board.legal_moves().len();
+            board.move_legally(*from, *to);
+        }
+        // Moves times the average branching, which is simply the summed branching.
+        Difficulty::from_score(branching)
+    }
+
+    pub fn efficiency_percent(moves: usize, par: usize) -> usize {
+        if moves <= par {
+            return 100;
+        }
+        (par * 100 + moves / 2) / moves
+    }
+
+    pub fn star_rating(moves: usize, par: usize) -> usize {
+        match Game::efficiency_percent(moves, par) {
+            efficiency if efficiency >= THREE_STAR_EFFICIENCY => MAX_STARS,
+            efficiency if efficiency >= TWO_STAR_EFFICIENCY => 2,
+            _ => 1,
+        }
+    }
+
+    pub fn completion_summary(&self, style: RenderStyle) -> String {
+        let moves: usize = self.get_move_count();
+        match self.get_par() {
+            Some(par) => {
+                let stars: usize = Game::star_rating(moves, par);
+                format!(
+                    "Completed in {} moves (par {}) {} {}% efficiency {}",
+                    moves,
+                    par,
+                    style.dash(),
+                    Game::efficiency_percent(moves, par),
+                    style.stars(stars, MAX_STARS)
+                )
+            }
+            None => format!("Completed in {} moves", moves),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_star_rating() {
+        assert_eq!(Game::star_rating(6, 6), MAX_STARS);
+        assert_eq!(Game::star_rating(7, 6), 2);
+        assert_eq!(Game::star_rating(9, 6), 1);
+        assert_eq!(Game::efficiency_percent(9, 6), 67);
+    }
+
+    #[test]
+    fn test_difficulty_ratings() {
+        let one_move: Game = Game::new_from_vecs(vec![vec![1, 0], vec![1, 0]], None);
+        assert_eq!(one_move.difficulty(), Difficulty::Easy);
+
+        let mut longer: Game = Game::new_from_vecs(
+            vec![
+                vec![1, 2, 3, 1],
+                vec![2, 3, 1, 2],
+                vec![3, 1, 2, 3],
+                vec![0, 0, 0, 0],
+                vec![0, 0, 0, 0],
+            ],
+            None,
+        );
+        assert!(longer.difficulty() > Difficulty::Easy);
+
+        longer.move_legally(0, 3); // A move means the cached rating no longer applies.
+        assert!(longer.cached_difficulty().is_none());
+        assert_eq!(Difficulty::from_score(HARD_SCORE + 1), Difficulty::Expert);
+    }
+
+    #[test]
+    fn test_completion_summary_styles() {
+        let mut game: Game = Game::new_from_vecs(vec![vec![1, 0], vec![1, 0]], None);
+        game.compute_par();
+        game.move_legally(0, 1);
+        assert_eq!(
+            game.completion_summary(RenderStyle::Color),
+            "Completed in 1 moves (par 1) — 100% efficiency ★★★"
+        );
+        assert_eq!(
+            game.completion_summary(RenderStyle::Ascii),
+            "Completed in 1 moves (par 1) - 100% efficiency ***"
+        );
+    }
+}
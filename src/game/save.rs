@@ -0,0 +1,307 @@
+use crate::game::config;
+use crate::game::entry::Entry;
+use crate::game::error::LoadError;
+use crate::game::stack::Stack;
+use crate::game::Game;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const SAVE_FILE_NAME: &str = "save.json";
+
+impl Game {
+    pub fn default_save_path() -> Option<PathBuf> {
+        config::data_dir().map(|dir| dir.join(SAVE_FILE_NAME))
+    }
+
+    pub fn save_to_path(&self, path: &Path) -> io::Result<()> {
+        let json: String = serde_json::to_string_pretty(self)?;
+        config::write_atomically(path, json.as_bytes())
+    }
+
+    pub fn load_from_path(path: &Path) -> Result<Game, LoadError> {
+        let json: String = fs::read_to_string(path)?;
+        let mut game: Game =
+            serde_json::from_str(&json).map_err(|error| LoadError::Parse(error.to_string()))?;
+        game.validate().map_err(LoadError::Inconsistent)?;
+        if game.initial_stacks.is_empty() {
+            game.initial_stacks = game.starting_layout().map_err(LoadError::Inconsistent)?;
+        }
+        Ok(game)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if let Some(ind) = self.stacks.iter().position(|stack| !stack.is_consistent()) {
+            return Err(format!(
+                "stack {} does not match its recorded occupancy",
+                ind + 1
+            ));
+        }
+        if Game::count_kinds(&self.stacks) != self.units_per_kind {
+            return Err("unit counts per kind do not match the stacks".to_string());
+        }
+        if Game::index_kinds(&self.units_per_kind) != self.kind_indices {
+            return Err("kind indices do not match the kinds on the board".to_string());
+        }
+        if self.turn != self.ledger.len() + 1 {
+            return Err("the turn does not follow the moves made".to_string());
+        }
+        let players: usize = match self.two_player {
+            true => 2,
+            false => 1,
+        };
+        if self.current_player >= players {
+            return Err("the player to move does not exist".to_string());
+        }
+        let stack_count: usize = self.stacks.len();
+        let entries_in_range: bool = self
+            .ledger
+            .iter()
+            .chain(self.redo_ledger.iter())
+            .all(|entry| entry.from < stack_count && entry.to < stack_count);
+        if !entries_in_range {
+            return Err("the move history refers to stacks that do not exist".to_string());
+        }
+        let initial_fits: bool = self.initial_stacks.is_empty()
+            || (self.initial_stacks.len() == stack_count
+                && Game::count_kinds(&self.initial_stacks) == self.units_per_kind
+                && self
+                    .initial_stacks
+                    .iter()
+                    .zip(&self.stacks)
+                    .all(|(start, now)| {
+                        start.get_capacity() == now.get_capacity()
+                            && start.get_lock() == now.get_lock()
+                    }));
+        if !initial_fits {
+            return Err("the starting layout does not match the board".to_string());
+        }
+        if let Some(ind) = self
+            .initial_stacks
+            .iter()
+            .position(|stack| !stack.is_consistent())
+        {
+            return Err(format!(
+                "stack {} of the starting layout does not match its recorded occupancy",
+                ind + 1
+            ));
+        }
+        let recomputed: Game = Game::new(self.stacks.clone(), None);
+        if recomputed.kinds_status != self.kinds_status {
+            return Err("sorted kinds do not match the stacks".to_string());
+        }
+        // Undo, redo and reset all trust the history, so it has to play out as recorded.
+        let start: Vec<Stack> = self.starting_layout()?;
+        let replayed: Game = Game::replay_recorded(Game::new(start, None), &self.ledger, "move")?;
+        let lands_on_board: bool = replayed
+            .stacks
+            .iter()
+            .zip(&self.stacks)
+            .all(|(made, saved)| made.get_units() == saved.get_units());
+        if !lands_on_board {
+            return Err("the move history does not lead to the saved board".to_string());
+        }
+        let redos: Vec<Entry> = self.redo_ledger.iter().rev().cloned().collect();
+        Game::replay_recorded(recomputed, &redos, "undone move")?;
+        Ok(())
+    }
+
+    // Older saves have no starting layout, so theirs is found by taking every move back.
+    fn starting_layout(&self) -> Result<Vec<Stack>, String> {
+        if !self.initial_stacks.is_empty() {
+            return Ok(self.initial_stacks.clone());
+        }
+        let mut start: Game = self.clone();
+        start.turn = self.ledger.len() + 1; // Every move taken back counts the turn down.
+        for (ind, entry) in self.ledger.iter().enumerate().rev() {
+            if !start.take_back(entry) {
+                return Err(format!("move {} can't be taken back", ind + 1));
+            }
+        }
+        Ok(start.stacks)
+    }
+
+    // Each entry has to be accepted as a move and pour what it recorded.
+    fn replay_recorded(mut game: Game, entries: &[Entry], name: &str) -> Result<Game, String> {
+        game.replay(entries).map_err(|error| {
+            format!(
+                "{} {} can't be replayed: {}",
+                name,
+                error.index + 1,
+                error.error
+            )
+        })?;
+        let mismatch: Option<usize> =
+            game.ledger
+                .iter()
+                .zip(entries)
+                .position(|(made, recorded)| {
+                    made.kind != recorded.kind
+                        || (recorded.source_len != 0 && made.source_len != recorded.source_len)
+                });
+        match mismatch {
+            Some(ind) => Err(format!(
+                "{} {} does not match the board it was made on",
+                name,
+                ind + 1
+            )),
+            None => Ok(game),
+        }
+    }
+
+    pub fn offer_resume() -> Option<Game> {
+        let path: PathBuf = Game::default_save_path()?;
+        if !path.exists() {
+            return None;
+        }
+        match Game::load_from_path(&path) {
+            Ok(game) if game.confirm("A saved game was found. Resume it?") => Some(game),
+            Ok(_) => None,
+            Err(error) => {
+                eprintln!("Ignoring the saved game: {}", error);
+                None
+            }
+        }
+    }
+
+    pub fn discard_save() {
+        if let Some(path) = Game::default_save_path() {
+            let _ = fs::remove_file(path); // Nothing to discard if it was never saved.
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::daily::Date;
+    use crate::game::stack::kind::Kind;
+
+    #[test]
+    fn test_save_and_reload() {
+        let dir: PathBuf =
+            std::env::temp_dir().join(format!("sorting-game-save-{}", std::process::id()));
+        let path: PathBuf = dir.join(SAVE_FILE_NAME);
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        game.move_legally(0, 2);
+        game.save_to_path(&path).unwrap();
+        game.move_legally(1, 0);
+        game.save_to_path(&path).unwrap(); // Overwrites the previous save.
+
+        let restored: Game = Game::load_from_path(&path).unwrap();
+        assert!(restored.stacks == game.stacks);
+        assert_eq!(restored.ledger, game.ledger);
+        assert_eq!(restored.turn, game.turn);
+        assert_eq!(restored.stage_name, game.stage_name);
+        assert_eq!(restored.kinds_status, game.kinds_status);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1); // No temp file left behind.
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_resumed_daily_keeps_its_date() {
+        let dir: PathBuf =
+            std::env::temp_dir().join(format!("sorting-game-daily-{}", std::process::id()));
+        let path: PathBuf = dir.join(SAVE_FILE_NAME);
+        let date: Date = Date::new(2026, 10, 15);
+        Game::daily(date).save_to_path(&path).unwrap();
+        assert_eq!(Game::load_from_path(&path).unwrap().get_daily(), Some(date));
+
+        Game::get_stages()[0].save_to_path(&path).unwrap();
+        assert_eq!(Game::load_from_path(&path).unwrap().get_daily(), None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_rejects_corrupt_files() {
+        let dir: PathBuf =
+            std::env::temp_dir().join(format!("sorting-game-corrupt-{}", std::process::id()));
+        let path: PathBuf = dir.join(SAVE_FILE_NAME);
+        let game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        game.save_to_path(&path).unwrap();
+
+        let json: String = fs::read_to_string(&path).unwrap();
+        fs::write(&path, &json[..json.len() / 2]).unwrap();
+        assert!(matches!(
+            Game::load_from_path(&path),
+            Err(LoadError::Parse(_))
+        ));
+
+        let mut miscounted: Game = game.clone();
+        miscounted.units_per_kind.insert(1, 5);
+        let mut turn_zero: Game = game.clone();
+        turn_zero.turn = 0;
+        let mut missing_player: Game = game.clone();
+        missing_player.set_two_player(true);
+        missing_player.current_player = 5;
+        let mut solo_second_player: Game = game.clone();
+        solo_second_player.current_player = 1;
+        let mut all_hidden: Game = game.clone();
+        all_hidden.hide_units();
+        let json: String =
+            serde_json::to_string(&all_hidden)
+                .unwrap()
+                .replacen("\"hidden\":1", "\"hidden\":9", 1);
+        for tampered in [miscounted, turn_zero, missing_player, solo_second_player] {
+            tampered.save_to_path(&path).unwrap();
+            assert!(matches!(
+                Game::load_from_path(&path),
+                Err(LoadError::Inconsistent(_))
+            ));
+        }
+        fs::write(&path, json).unwrap();
+        assert!(matches!(
+            Game::load_from_path(&path),
+            Err(LoadError::Inconsistent(_))
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_rejects_a_history_that_does_not_fit() {
+        let dir: PathBuf =
+            std::env::temp_dir().join(format!("sorting-game-history-{}", std::process::id()));
+        let path: PathBuf = dir.join(SAVE_FILE_NAME);
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        game.run_script(&[(0, 2), (1, 0)]).unwrap();
+
+        let mut swapped: Game = game.clone();
+        swapped.ledger.swap(0, 1);
+        let mut wrong_kind: Game = game.clone();
+        wrong_kind.ledger[1].kind = Kind::new(2, 1);
+        let mut old_and_wrong: Game = game.clone();
+        old_and_wrong.initial_stacks.clear(); // An older save, walked back from the board.
+        old_and_wrong.ledger[0].to = 1;
+        let mut bad_redo: Game = game.clone();
+        bad_redo.undo_move();
+        bad_redo.redo_ledger[0].from = 2;
+        let mut resized: Game = game.clone();
+        resized.initial_stacks[2] = Stack::with_capacity(4);
+        let mut shuffled_start: Game = game.clone();
+        shuffled_start.initial_stacks.swap(0, 1); // Same units and capacities, other moves.
+        for tampered in [
+            swapped,
+            wrong_kind,
+            old_and_wrong,
+            bad_redo,
+            resized,
+            shuffled_start,
+        ] {
+            tampered.save_to_path(&path).unwrap();
+            assert!(matches!(
+                Game::load_from_path(&path),
+                Err(LoadError::Inconsistent(_))
+            ));
+        }
+
+        let mut old: Game = game.clone();
+        old.initial_stacks.clear();
+        old.save_to_path(&path).unwrap();
+        let restored: Game = Game::load_from_path(&path).unwrap();
+        assert!(restored.initial_layout() == game.initial_layout());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,215 @@
+#[cfg(feature = "serde")]
+use crate::game::config;
+use crate::game::daily::Date;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+#[cfg(feature = "serde")]
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "serde")]
+const SCORES_FILE_NAME: &str = "scores.json";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BestScore {
+    pub moves: usize,
+    pub time: u64, // Seconds spent on the stage.
+}
+
+impl BestScore {
+    pub fn beats(&self, other: &BestScore) -> bool {
+        (self.moves, self.time) < (other.moves, other.time)
+    }
+}
+
+#[derive(Default, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Leaderboard {
+    stages: HashMap<String, BestScore>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    daily: HashMap<String, BestScore>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(not(feature = "serde"), allow(dead_code))] // Only read when scores are stored.
+    path: Option<PathBuf>,
+}
+
+impl Leaderboard {
+    #[cfg(feature = "serde")]
+    pub fn default_path() -> Option<PathBuf> {
+        config::data_dir().map(|dir| dir.join(SCORES_FILE_NAME))
+    }
+
+    #[cfg(not(feature = "serde"))]
+    pub fn default_path() -> Option<PathBuf> {
+        None
+    }
+
+    pub fn load() -> Leaderboard {
+        match Leaderboard::default_path() {
+            Some(path) => Leaderboard::load_from_path(&path),
+            None => Leaderboard::default(),
+        }
+    }
+
+    // A missing or unreadable scores file starts a fresh leaderboard rather than failing.
+    #[cfg(feature = "serde")]
+    pub fn load_from_path(path: &Path) -> Leaderboard {
+        let mut leaderboard: Leaderboard = match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|error| {
+                eprintln!("Ignoring corrupt scores file {}: {}", path.display(), error);
+                Leaderboard::default()
+            }),
+            Err(_) => Leaderboard::default(),
+        };
+        leaderboard.path = Some(path.to_path_buf());
+        leaderboard
+    }
+
+    #[cfg(not(feature = "serde"))]
+    pub fn load_from_path(path: &Path) -> Leaderboard {
+        Leaderboard {
+            path: Some(path.to_path_buf()),
+            ..Leaderboard::default()
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn store(&self) -> io::Result<()> {
+        match &self.path {
+            Some(path) => {
+                let json: String = serde_json::to_string_pretty(self)?;
+                config::write_atomically(path, json.as_bytes())
+            }
+            None => Ok(()), // Nowhere to persist to, keep the scores in memory only.
+        }
+    }
+
+    #[cfg(not(feature = "serde"))]
+    pub fn store(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    pub fn get(&self, stage_name: &str) -> Option<&BestScore> {
+        self.stages.get(stage_name)
+    }
+
+    pub fn get_daily(&self, date: Date) -> Option<&BestScore> {
+        self.daily.get(&date.to_string())
+    }
+
+    pub fn record(&mut self, stage_name: &str, score: BestScore) -> bool {
+        Leaderboard::record_into(&mut self.stages, stage_name.to_string(), score)
+    }
+
+    pub fn record_daily(&mut self, date: Date, score: BestScore) -> bool {
+        Leaderboard::record_into(&mut self.daily, date.to_string(), score)
+    }
+
+    // Consecutive days with the daily challenge completed, up to `today`. A streak isn't broken
+    // until a whole day passes without one, so it still counts before today's is done.
+    pub fn daily_streak(&self, today: Date) -> usize {
+        let mut day: Date = match self.get_daily(today) {
+            Some(_) => today,
+            None => today.previous(),
+        };
+        let mut streak: usize = 0;
+        while self.get_daily(day).is_some() {
+            streak += 1;
+            day = day.previous();
+        }
+        streak
+    }
+
+    fn record_into(scores: &mut HashMap<String, BestScore>, key: String, score: BestScore) -> bool {
+        let is_new_best: bool = match scores.get(&key) {
+            Some(best) => score.beats(best),
+            None => true,
+        };
+        if is_new_best {
+            scores.insert(key, score);
+        }
+        is_new_best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_score_is_recorded() {
+        let mut leaderboard: Leaderboard = Leaderboard::default();
+        let score: BestScore = BestScore { moves: 9, time: 40 };
+        assert!(leaderboard.record("Stage - 1", score));
+        assert_eq!(leaderboard.get("Stage - 1"), Some(&score));
+        assert_eq!(leaderboard.get("Stage - 2"), None);
+    }
+
+    #[test]
+    fn test_only_better_scores_replace_the_record() {
+        let mut leaderboard: Leaderboard = Leaderboard::default();
+        leaderboard.record("Stage - 1", BestScore { moves: 9, time: 40 });
+
+        assert!(!leaderboard.record("Stage - 1", BestScore { moves: 10, time: 5 }));
+        assert!(!leaderboard.record("Stage - 1", BestScore { moves: 9, time: 40 }));
+        assert!(leaderboard.record("Stage - 1", BestScore { moves: 9, time: 30 }));
+        assert!(leaderboard.record("Stage - 1", BestScore { moves: 7, time: 90 }));
+        assert_eq!(
+            leaderboard.get("Stage - 1"),
+            Some(&BestScore { moves: 7, time: 90 })
+        );
+    }
+
+    #[test]
+    fn test_daily_results_are_kept_apart() {
+        let mut leaderboard: Leaderboard = Leaderboard::default();
+        let date: Date = Date::new(2026, 10, 14);
+        assert!(leaderboard.record_daily(date, BestScore { moves: 8, time: 60 }));
+        assert_eq!(leaderboard.get(&date.to_string()), None);
+        assert_eq!(
+            leaderboard.get_daily(date),
+            Some(&BestScore { moves: 8, time: 60 })
+        );
+    }
+
+    #[test]
+    fn test_daily_streak() {
+        let mut leaderboard: Leaderboard = Leaderboard::default();
+        let score: BestScore = BestScore { moves: 8, time: 60 };
+        assert_eq!(leaderboard.daily_streak(Date::new(2026, 2, 27)), 0);
+
+        leaderboard.record_daily(Date::new(2026, 2, 27), score);
+        leaderboard.record_daily(Date::new(2026, 2, 28), score);
+        assert_eq!(leaderboard.daily_streak(Date::new(2026, 2, 28)), 2);
+        assert_eq!(leaderboard.daily_streak(Date::new(2026, 3, 1)), 2); // Not done yet today.
+        leaderboard.record_daily(Date::new(2026, 3, 1), score);
+        assert_eq!(leaderboard.daily_streak(Date::new(2026, 3, 1)), 3);
+
+        // Skipping a day starts over.
+        assert_eq!(leaderboard.daily_streak(Date::new(2026, 3, 3)), 0);
+        leaderboard.record_daily(Date::new(2026, 3, 3), score);
+        assert_eq!(leaderboard.daily_streak(Date::new(2026, 3, 3)), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_corrupt_file_starts_fresh() {
+        let path: PathBuf =
+            std::env::temp_dir().join(format!("sorting-game-scores-{}.json", std::process::id()));
+        fs::write(&path, "{ not json").unwrap();
+        let mut leaderboard: Leaderboard = Leaderboard::load_from_path(&path);
+        assert_eq!(leaderboard.get("Stage - 1"), None);
+
+        leaderboard.record("Stage - 1", BestScore { moves: 3, time: 1 });
+        leaderboard.store().unwrap();
+        let reloaded: Leaderboard = Leaderboard::load_from_path(&path);
+        assert_eq!(
+            reloaded.get("Stage - 1"),
+            Some(&BestScore { moves: 3, time: 1 })
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}
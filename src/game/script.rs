@@ -0,0 +1,82 @@
+use crate::game::error::{MoveError, ScriptError};
+use crate::game::Game;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ScriptReport {
+    pub complete: bool,
+    pub accepted: usize,
+    pub rejected: Option<(usize, MoveError)>,
+}
+
+impl Game {
+    // Applies the moves silently, stopping at the first one the board rejects. Only a move
+    // naming a stack that doesn't exist is an error, since the script itself is then broken;
+    // the moves accepted before it remain applied.
+    pub fn run_script(&mut self, moves: &[(usize, usize)]) -> Result<ScriptReport, ScriptError> {
+        let mut report: ScriptReport = ScriptReport {
+            complete: self.stage_complete(),
+            accepted: 0,
+            rejected: None,
+        };
+        for (index, (from, to)) in moves.iter().enumerate() {
+            match self.try_move(*from, *to) {
+                Ok(()) => report.accepted += 1,
+                Err(MoveError::OutOfBounds) => {
+                    return Err(ScriptError {
+                        index,
+                        accepted: report.accepted,
+                        error: MoveError::OutOfBounds,
+                    })
+                }
+                Err(error) => {
+                    report.rejected = Some((index, error));
+                    break;
+                }
+            }
+        }
+        report.complete = self.stage_complete();
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_winning_script_completes_stage() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        let report: ScriptReport = game.run_script(&[(0, 2), (1, 0), (1, 2)]).unwrap();
+        assert_eq!(
+            report,
+            ScriptReport {
+                complete: true,
+                accepted: 3,
+                rejected: None
+            }
+        );
+        assert!(game.stage_complete());
+    }
+
+    #[test]
+    fn test_script_stops_at_rejected_move() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        let report: ScriptReport = game.run_script(&[(0, 2), (0, 2), (1, 0)]).unwrap();
+        assert_eq!(report.accepted, 1);
+        assert_eq!(report.rejected, Some((1, MoveError::TopMismatch)));
+        assert!(!report.complete);
+        assert_eq!(game.get_move_count(), 1);
+
+        assert_eq!(
+            game.run_script(&[(1, 0), (7, 0)]),
+            Err(ScriptError {
+                index: 1,
+                accepted: 1,
+                error: MoveError::OutOfBounds
+            })
+        );
+        assert_eq!(game.get_move_count(), 2);
+    }
+}
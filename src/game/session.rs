@@ -0,0 +1,677 @@
+use crate::game::achievements::{Achievement, Achievements, Progress};
+use crate::game::daily::Date;
+use crate::game::error::GameError;
+use crate::game::gui;
+use crate::game::input;
+use crate::game::observer::Observer;
+use crate::game::rng;
+use crate::game::scores::{BestScore, Leaderboard};
+use crate::game::solver::SearchOutcome;
+use crate::game::stats::SessionStats;
+use crate::game::style::Settings;
+use crate::game::timer::StageTimer;
+use crate::game::Game;
+use std::sync::Arc;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LoopOutcome {
+    Completed,
+    Failed,  // The move limit ran out before the stage was sorted.
+    Skipped, // Passed over with the developer option, counts as done but scores nothing.
+    Quit,
+}
+
+// The interactive side of the game: the turn loop and stage flow driven through the terminal.
+// Everything a headless caller needs lives in the rest of the module.
+impl Game {
+    fn show_hint(&mut self) {
+        if self.stage_complete() {
+            self.message = Some("The stage is already sorted!".to_string());
+            return;
+        }
+        if self.has_face_down_units() {
+            self.message = Some(Game::face_down_prompt());
+            return;
+        }
+        self.message = Some(match self.hint_search() {
+            SearchOutcome::Solved(solution) => {
+                let (from, to): (usize, usize) = solution[0]; // Not sorted, so it takes a move.
+                self.highlight = Some((from, to));
+                format!("Hint: move stack {} to {}", from + 1, to + 1)
+            }
+            SearchOutcome::Exhausted => {
+                "No hint available, the stage can't be solved from here".to_string()
+            }
+            SearchOutcome::GaveUp => "No hint available, the search ran out of time".to_string(),
+        });
+    }
+
+    fn show_moves_left(&mut self) {
+        if self.has_face_down_units() {
+            self.message = Some(Game::face_down_prompt());
+            return;
+        }
+        self.message = Some(match self.moves_remaining() {
+            Some(0) => "The stage is already sorted!".to_string(),
+            Some(1) => "Solvable in 1 more move".to_string(),
+            Some(moves) => format!("Solvable in {} more moves", moves),
+            None => "No solution found from here, try undoing a few moves".to_string(),
+        });
+    }
+
+    #[cfg(feature = "serde")]
+    fn save_game(&mut self) {
+        // The tutorial's guidance isn't saved, so a resumed tutorial would be a scored stage.
+        if self.tutorial.is_some() {
+            self.message = Some("The tutorial can't be saved".to_string());
+            return;
+        }
+        let message: String = match Game::default_save_path() {
+            Some(path) => match self.save_to_path(&path) {
+                Ok(()) => format!("Game saved to {}", path.display()),
+                Err(error) => format!("Could not save the game: {}", error),
+            },
+            None => "Could not save the game: no home directory found".to_string(),
+        };
+        self.message = Some(message);
+    }
+
+    #[cfg(not(feature = "serde"))]
+    fn save_game(&mut self) {
+        self.message = Some("Saving requires the serde feature".to_string());
+    }
+
+    // How the stage ended, if it has.
+    pub(crate) fn outcome(&self) -> Option<LoopOutcome> {
+        if self.stage_complete() {
+            return Some(LoopOutcome::Completed);
+        }
+        match self.move_limit {
+            Some(limit) if self.get_move_count() >= limit => Some(LoopOutcome::Failed),
+            _ => None,
+        }
+    }
+
+    // True only the first time it's asked after the stage was sorted, so the win is announced once.
+    fn first_time_complete(&mut self) -> bool {
+        if !self.stage_complete() {
+            self.celebrated = false;
+            return false;
+        }
+        !std::mem::replace(&mut self.celebrated, true)
+    }
+
+    // Resetting keeps the clock running and the undos counted, but restores the undo budget.
+    fn reset(&mut self) {
+        let mut before: Game = self.clone();
+        before.before_reset = None; // Only the latest reset can be taken back.
+        self.restart();
+        self.set_coaching(self.coaching);
+        self.undos_used = 0; // A fresh start gets the whole undo budget back.
+        self.message = None;
+        self.highlight = None;
+        self.selected = None;
+        self.celebrated = false;
+        self.before_reset = Some(Arc::new(before));
+    }
+
+    fn undo_reset(&mut self) {
+        if let Some(before) = self.before_reset.take() {
+            let timer: StageTimer = self.timer;
+            let (observer, coaching): (Observer, bool) =
+                (std::mem::take(&mut self.observer), self.coaching);
+            *self = (*before).clone();
+            self.timer = timer;
+            self.observer = observer;
+            self.set_coaching(coaching);
+            self.message = Some("The reset was undone".to_string());
+        }
+    }
+
+    // Only a player's own win on the stage's own board reaches the leaderboard and achievements.
+    // A shared game is neither player's, a reshuffled board isn't the stage's, and the tutorial
+    // and the solver both spell out the moves.
+    fn is_scored(&self) -> bool {
+        !(self.two_player || self.reshuffled || self.tutorial.is_some() || self.auto_solved)
+    }
+
+    // Par is measured from the starting layout only, unless the stage sets its own.
+    pub(crate) fn settle_par(&mut self) {
+        match self.meta.par {
+            Some(par) => self.par = Some(par),
+            None => self.compute_par(),
+        }
+    }
+
+    fn turn_loop(&mut self, source: &mut dyn gui::InputSource) -> LoopOutcome {
+        if self.ledger.is_empty() && self.par.is_none() {
+            self.settle_par();
+        }
+        self.resume();
+        loop {
+            match self.outcome() {
+                Some(LoopOutcome::Failed) if self.confirm_retry() => {
+                    self.reset();
+                    continue;
+                }
+                Some(outcome) => {
+                    self.pause(); // The final time stays on screen.
+                    if self.first_time_complete() && !self.quiet {
+                        Game::ring_bell();
+                    }
+                    return outcome;
+                }
+                None => {}
+            }
+            if self.is_stuck() {
+                self.message = Some(format!(
+                    "No legal moves left, the stage can't be solved from here!\nPress '{}' to undo or '{}' to reset",
+                    self.keys.key_for(gui::MenuOption::Undo),
+                    self.keys.key_for(gui::MenuOption::Reset)
+                ));
+            }
+            self.guide_tutorial();
+            let user_input: gui::UserInput = source.next_input(self);
+            self.highlight = None; // Hints are only highlighted until the next action.
+            match user_input.stack_move {
+                Some((from, to)) if !self.tutorial_accepts(from, to) => {
+                    self.correct_tutorial_move();
+                }
+                Some((from, to)) => {
+                    let turn: usize = self.turn;
+                    if let Some(added) = self.coached_move(from, to) {
+                        self.message = Some(match added {
+                            1 => "That move added a step".to_string(),
+                            added => format!("That move added {} steps", added),
+                        });
+                    }
+                    // Two players take every move themselves.
+                    if self.fast_forward && !self.two_player && self.turn != turn {
+                        self.watch_fast_forward();
+                    }
+                }
+                _ => match user_input.menu_option {
+                    gui::MenuOption::Help => self.show_help(),
+                    gui::MenuOption::Quit if self.confirm("Quit the game?") => {
+                        self.pause();
+                        return LoopOutcome::Quit;
+                    }
+                    // Moves stand once made by two players, however they'd be taken back.
+                    gui::MenuOption::Reset
+                    | gui::MenuOption::UndoAll
+                    | gui::MenuOption::Jump
+                    | gui::MenuOption::Reshuffle
+                        if self.two_player =>
+                    {
+                        self.message =
+                            Some("Moves can't be taken back in two-player games".to_string());
+                    }
+                    gui::MenuOption::Reset
+                        if self.confirm("Reset the stage to its starting layout?") =>
+                    {
+                        self.reset();
+                        self.message = Some(format!(
+                            "Stage reset, press '{}' to take it back",
+                            self.keys.key_for(gui::MenuOption::Undo)
+                        ));
+                    }
+                    gui::MenuOption::Undo if self.before_reset.is_some() => self.undo_reset(),
+                    gui::MenuOption::Undo => self.undo_move(),
+                    gui::MenuOption::UndoAll => self.watch_undo_all(),
+                    gui::MenuOption::Redo => self.redo_move(),
+                    gui::MenuOption::Jump => {
+                        if let Some(position) = self.ask_jump_position() {
+                            self.jump_to(position);
+                        }
+                    }
+                    gui::MenuOption::Save => self.save_game(),
+                    gui::MenuOption::Hint => self.show_hint(),
+                    gui::MenuOption::MovesLeft => self.show_moves_left(),
+                    gui::MenuOption::Coach => {
+                        self.set_coaching(!self.coaching);
+                        self.message = Some(
+                            match self.coaching {
+                                true => "Coaching on, moves off the best path are pointed out",
+                                false => "Coaching off",
+                            }
+                            .to_string(),
+                        );
+                    }
+                    gui::MenuOption::AutoSolve => self.watch_auto_solve(),
+                    gui::MenuOption::Reshuffle if self.tutorial.is_some() => {
+                        self.message = Some("The tutorial keeps its own board".to_string());
+                    }
+                    gui::MenuOption::Reshuffle
+                        if self.confirm("Shuffle a new board? Every move so far is discarded") =>
+                    {
+                        self.message = Some(
+                            match self.reshuffle(rng::clock_seed()) {
+                                true => {
+                                    self.settle_par();
+                                    "A new board, the same units"
+                                }
+                                false => "These units can't be sorted into a new board",
+                            }
+                            .to_string(),
+                        );
+                    }
+                    gui::MenuOption::History => self.show_history = !self.show_history,
+                    gui::MenuOption::Pause => self.show_pause_screen(),
+                    gui::MenuOption::Skip if self.dev => {
+                        self.pause();
+                        return LoopOutcome::Skipped;
+                    }
+                    _ => {}
+                },
+            }
+        }
+    }
+
+    #[cfg(not(feature = "serde"))]
+    fn offer_resume() -> Option<Game> {
+        None
+    }
+
+    #[cfg(not(feature = "serde"))]
+    fn discard_save() {}
+
+    fn play_stage(
+        mut stage: Game,
+        settings: &Settings,
+        is_last_stage: bool,
+        leaderboard: &mut Leaderboard,
+        daily: Option<Date>,
+        stats: &mut SessionStats,
+    ) -> LoopOutcome {
+        stage.style = settings.style;
+        stage.empty_look = settings.empty_look;
+        stage.compact_runs = settings.compact_runs;
+        stage.palette = settings.palette.clone();
+        stage.explicit_moves = settings.explicit_moves;
+        stage.keys = settings.keys.clone();
+        stage.quiet = settings.quiet;
+        stage.dev = settings.dev;
+        stage.cost_model = settings.cost_model;
+        stage.set_fast_forward(settings.fast_forward);
+        if settings.face_down && stage.ledger.is_empty() {
+            stage.hide_units(); // A resumed game keeps what was already revealed.
+        }
+        if settings.two_player && !stage.two_player {
+            stage.set_two_player(true); // A resumed two-player game keeps whose turn it was.
+        }
+        if stage.undo_limit.is_none() {
+            stage.undo_limit = settings.undo_limit;
+        }
+        if !settings.fast && stage.ledger.is_empty() {
+            stage.settle_par(); // Shown on the intro, and kept for the turn loop.
+            let best: Option<&BestScore> = match daily {
+                Some(date) => leaderboard.get_daily(date),
+                None => leaderboard.get(&stage.stage_name),
+            };
+            stage.show_intro(best);
+        }
+        let outcome: LoopOutcome = stage.turn_loop(&mut gui::TerminalInput);
+        if outcome == LoopOutcome::Completed && !stage.is_scored() {
+            stage.play_win_animation();
+            stage.stage_complete_prompt(is_last_stage, false, None, &[]);
+        } else if outcome == LoopOutcome::Completed {
+            let score: BestScore = BestScore {
+                moves: stage.get_move_count(),
+                time: stage.get_elapsed().as_secs(),
+            };
+            let is_new_best: bool = match daily {
+                Some(date) => leaderboard.record_daily(date, score),
+                None => leaderboard.record(&stage.stage_name, score),
+            };
+            if let Err(error) = leaderboard.store() {
+                eprintln!("Could not save the scores: {}", error);
+            }
+            let mut achievements: Achievements = Achievements::load();
+            let unlocked: Vec<&Achievement> = achievements.unlock_new(&Progress {
+                stage: &stage,
+                leaderboard,
+                today: daily.unwrap_or_else(Date::today_utc),
+            });
+            if !unlocked.is_empty() {
+                if let Err(error) = achievements.store() {
+                    eprintln!("Could not save the achievements: {}", error);
+                }
+            }
+            let best: Option<&BestScore> = match daily {
+                Some(date) => leaderboard.get_daily(date),
+                None => leaderboard.get(&stage.stage_name),
+            };
+            stage.play_win_animation();
+            stage.stage_complete_prompt(is_last_stage, is_new_best, best, &unlocked);
+        }
+        stats.record(&stage, outcome);
+        outcome
+    }
+
+    fn play_in_order(
+        stages: &[Game],
+        settings: &Settings,
+        leaderboard: &mut Leaderboard,
+        stats: &mut SessionStats,
+    ) -> LoopOutcome {
+        let last_stage_index: usize = stages.len() - 1;
+        for (ind, stage) in stages.iter().enumerate() {
+            let mut stage: Game = stage.clone();
+            stage.progress = Some((ind + 1, stages.len()));
+            let outcome: LoopOutcome = Game::play_stage(
+                stage,
+                settings,
+                ind == last_stage_index,
+                leaderboard,
+                None,
+                stats,
+            );
+            if !matches!(outcome, LoopOutcome::Completed | LoopOutcome::Skipped) {
+                return outcome;
+            }
+        }
+        LoopOutcome::Completed
+    }
+
+    pub fn play(settings: &Settings) -> Result<SessionStats, GameError> {
+        let mut stages: Vec<Game> = Game::user_or_builtin_stages();
+        if settings.check_solvable {
+            stages = Game::without_unsolvable(stages);
+        }
+        if !settings.no_sort {
+            stages = Game::sort_by_difficulty(stages);
+        }
+        if stages.is_empty() {
+            return Err(GameError::NoStages);
+        }
+        let last_stage_index: usize = stages.len() - 1;
+        let mut leaderboard: Leaderboard = Leaderboard::load();
+        let mut stats: SessionStats = SessionStats::default();
+
+        if let Some(saved) = Game::offer_resume() {
+            let is_last_stage: bool = stages[last_stage_index].stage_name == saved.stage_name;
+            let daily: Option<Date> = saved.get_daily();
+            match Game::play_stage(
+                saved,
+                settings,
+                is_last_stage,
+                &mut leaderboard,
+                daily,
+                &mut stats,
+            ) {
+                LoopOutcome::Completed | LoopOutcome::Failed | LoopOutcome::Skipped => {
+                    Game::discard_save() // The saved progress has been played through.
+                }
+                LoopOutcome::Quit => return Game::end_session(stats, settings),
+            }
+        }
+
+        let today: Date = Date::today_utc();
+        let mut daily: Game = Game::daily(today);
+        daily.compute_par();
+
+        loop {
+            let outcome: LoopOutcome =
+                match Game::choose_stage(&stages, &daily, today, &leaderboard, settings.style) {
+                    gui::StageChoice::Tutorial => Game::play_stage(
+                        Game::tutorial(),
+                        settings,
+                        false,
+                        &mut leaderboard,
+                        None,
+                        &mut stats,
+                    ),
+                    gui::StageChoice::Stage(ind) => Game::play_stage(
+                        stages[ind].clone(),
+                        settings,
+                        ind == last_stage_index,
+                        &mut leaderboard,
+                        None,
+                        &mut stats,
+                    ),
+                    gui::StageChoice::Daily => Game::play_stage(
+                        daily.clone(),
+                        settings,
+                        false,
+                        &mut leaderboard,
+                        Some(today),
+                        &mut stats,
+                    ),
+                    gui::StageChoice::All => {
+                        Game::play_in_order(&stages, settings, &mut leaderboard, &mut stats)
+                    }
+                    gui::StageChoice::Quit => LoopOutcome::Quit,
+                };
+            if outcome == LoopOutcome::Quit {
+                return Game::end_session(stats, settings);
+            }
+        }
+    }
+
+    // Plays a session with its input read from `lines` instead of the terminal.
+    pub fn play_with_script(
+        settings: &Settings,
+        lines: Vec<String>,
+    ) -> Result<SessionStats, GameError> {
+        input::with_script(lines, || Game::play(settings))
+    }
+
+    fn end_session(stats: SessionStats, settings: &Settings) -> Result<SessionStats, GameError> {
+        Game::restore_terminal(settings.style);
+        match input::is_closed() {
+            true => Err(GameError::InputClosed),
+            false => Ok(stats),
+        }
+    }
+
+    pub fn play_single(stage: Game, settings: &Settings) {
+        let mut leaderboard: Leaderboard = Leaderboard::load();
+        let mut stats: SessionStats = SessionStats::default();
+        Game::play_stage(stage, settings, true, &mut leaderboard, None, &mut stats);
+        Game::restore_terminal(settings.style);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::solver::AutoSolveOutcome;
+    use crate::game::style::RenderStyle;
+
+    #[test]
+    fn test_running_out_of_moves_fails_the_stage() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 0, 0], vec![2, 1, 0, 0], vec![0, 0, 0, 0]],
+            None,
+        );
+        game.set_move_limit(Some(2));
+        game.move_legally(0, 2);
+        assert_eq!(game.outcome(), None);
+        game.move_legally(0, 1);
+        assert_eq!(game.outcome(), Some(LoopOutcome::Failed));
+
+        game.undo_move(); // Undoing gives the move back.
+        assert_eq!(game.outcome(), None);
+    }
+
+    #[test]
+    fn test_reset_can_be_taken_back() {
+        let start: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 0, 0], vec![2, 1, 0, 0], vec![0, 0, 0, 0]],
+            None,
+        );
+        let mut game: Game = start.clone();
+        game.move_legally(0, 2);
+        game.move_legally(1, 0);
+        let before: Game = game.clone();
+
+        game.reset();
+        assert!(game.stacks == start.stacks);
+        assert!(game.ledger.is_empty());
+
+        game.undo_reset();
+        assert!(game.stacks == before.stacks);
+        assert_eq!(game.ledger, before.ledger);
+        assert!(game.before_reset.is_none()); // Only one level deep.
+        game.undo_reset();
+        assert!(game.stacks == before.stacks);
+
+        game.reset();
+        game.move_legally(0, 2); // Any change to the board drops the way back.
+        assert!(game.before_reset.is_none());
+        game.reset();
+        game.auto_solve(|_| false); // One move of the solution.
+        assert!(game.before_reset.is_none());
+    }
+
+    #[test]
+    fn test_completion_is_announced_once() {
+        let mut game: Game = Game::new_from_vecs(vec![vec![1, 0], vec![1, 0]], None);
+        assert!(!game.first_time_complete());
+        game.move_legally(0, 1);
+        assert!(game.first_time_complete());
+        assert!(!game.first_time_complete());
+
+        game.undo_move();
+        assert!(!game.first_time_complete());
+        game.redo_move();
+        assert!(game.first_time_complete()); // Sorting it again is a new completion.
+    }
+
+    #[test]
+    fn test_scripted_moves_complete_the_stage() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 0, 0], vec![2, 1, 0, 0], vec![0, 0, 0, 0]],
+            None,
+        );
+        game.quiet = true;
+        let mut script: Vec<gui::UserInput> = game
+            .solve()
+            .unwrap()
+            .into_iter()
+            .map(|(from, to)| gui::UserInput::new_move(from, to))
+            .collect();
+        script.insert(0, gui::UserInput::new_menu_option(gui::MenuOption::Hint));
+
+        assert_eq!(game.turn_loop(&mut script), LoopOutcome::Completed);
+        assert!(game.stage_complete());
+        assert!(script.is_empty());
+    }
+
+    #[test]
+    fn test_two_players_cannot_take_moves_back() {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 0, 0], vec![2, 1, 0, 0], vec![0, 0, 0, 0]],
+            None,
+        );
+        game.set_two_player(true);
+        let mut script: Vec<gui::UserInput> = vec![gui::UserInput::new_move(0, 2)];
+        for option in [
+            gui::MenuOption::Reset,
+            gui::MenuOption::Undo,
+            gui::MenuOption::UndoAll,
+            gui::MenuOption::Jump,
+            gui::MenuOption::Reshuffle,
+        ] {
+            script.push(gui::UserInput::new_menu_option(option));
+        }
+        let outcome: LoopOutcome =
+            input::with_script(vec!["y".to_string()], || game.turn_loop(&mut script));
+        assert_eq!(outcome, LoopOutcome::Quit);
+        assert_eq!(game.get_move_count(), 1);
+        assert!(game.before_reset.is_none());
+        assert!(!game.reshuffled);
+    }
+
+    #[test]
+    fn test_hint_tells_a_dead_board_from_a_search_that_gave_up() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        game.show_hint();
+        assert!(game.highlight.is_some());
+
+        let mut dead: Game = Game::new_from_vecs(vec![vec![1, 2], vec![2, 1]], None);
+        dead.show_hint();
+        assert!(dead.message.unwrap().contains("can't be solved"));
+        assert!(dead.highlight.is_none());
+
+        let mut tangled: Game = Game::get_stages().remove(1);
+        tangled.show_hint();
+        assert!(tangled.message.unwrap().contains("ran out of time"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tutorial_cannot_be_saved() {
+        let mut game: Game = Game::tutorial();
+        game.save_game();
+        assert_eq!(game.message.unwrap(), "The tutorial can't be saved");
+    }
+
+    #[test]
+    fn test_only_the_stages_own_board_is_scored() {
+        let stage: Game = Game::get_stages().remove(0);
+        assert!(stage.is_scored());
+        assert!(!Game::tutorial().is_scored());
+        let mut shared: Game = stage.clone();
+        shared.set_two_player(true);
+        assert!(!shared.is_scored());
+        let mut reshuffled: Game = stage.clone();
+        reshuffled.reshuffled = true;
+        assert!(!reshuffled.is_scored());
+
+        let mut solved: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        assert_eq!(solved.auto_solve(|_| false), AutoSolveOutcome::Stopped);
+        solved.reset(); // The solver's moves were still seen.
+        assert!(!solved.is_scored());
+    }
+
+    #[test]
+    fn test_dev_skip_moves_on_without_a_score() {
+        let stages: Vec<Game> = vec![
+            Game::new_from_vecs(
+                vec![vec![1, 2], vec![2, 1], vec![0, 0]],
+                Some("A".to_string()),
+            ),
+            Game::new_from_vecs(vec![vec![1, 0], vec![1, 0]], Some("B".to_string())),
+        ];
+        let settings: Settings = Settings {
+            style: RenderStyle::Ascii,
+            quiet: true,
+            fast: true,
+            dev: true,
+            ..Settings::default()
+        };
+        let mut leaderboard: Leaderboard = Leaderboard::default();
+        let mut stats: SessionStats = SessionStats::default();
+        let lines: Vec<String> = vec!["skip".to_string(), "skip".to_string()];
+        let outcome: LoopOutcome = input::with_script(lines, || {
+            Game::play_in_order(&stages, &settings, &mut leaderboard, &mut stats)
+        });
+        assert_eq!(outcome, LoopOutcome::Completed); // Both stages were passed.
+        assert_eq!(stats.stages_played, 2);
+        assert_eq!(stats.stages_completed, 2); // Skipping marks them done, without a score.
+        assert!(leaderboard.get("A").is_none());
+        assert!(leaderboard.get("B").is_none());
+        assert!(!input::is_closed());
+
+        // Without the flag it's just an unknown input, and the script runs out.
+        let settings: Settings = Settings {
+            dev: false,
+            ..settings
+        };
+        input::with_script(vec!["skip".to_string()], || {
+            Game::play_in_order(&stages, &settings, &mut leaderboard, &mut stats)
+        });
+        assert!(input::is_closed());
+    }
+
+    #[test]
+    fn test_sorting_on_the_last_move_completes() {
+        let mut game: Game = Game::new_from_vecs(vec![vec![1, 0], vec![1, 0]], None);
+        game.set_move_limit(Some(1));
+        game.move_legally(0, 1);
+        assert_eq!(game.outcome(), Some(LoopOutcome::Completed));
+    }
+}
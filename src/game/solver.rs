@@ -0,0 +1,455 @@
+use crate::game::stack::kind::{Kind, KindId};
+use crate::game::Game;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+// Bounds the number of boards explored for a hint, so asking for one never stalls the game.
+const HINT_SEARCH_LIMIT: usize = 20_000;
+const PAR_SEARCH_LIMIT: usize = 20_000;
+// Larger than the others, since it only runs once per stage and on request.
+const SOLVABILITY_SEARCH_LIMIT: usize = 100_000;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AutoSolveOutcome {
+    Solved,
+    Stopped,
+    NoSolution,
+}
+
+// Unknown when the search gave up before either finding a solution or running out of boards.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Solvability {
+    Solvable,
+    Unsolvable,
+    Unknown,
+}
+
+pub(crate) enum SearchOutcome {
+    Solved(Vec<(usize, usize)>),
+    Exhausted,
+    GaveUp,
+}
+
+// Stacks sorted by capacity, lock and contents, so boards differing only in stack order share
+// a key.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct StateKey(Vec<(usize, Option<KindId>, Vec<Kind>)>);
+
+// A board queued by A*, with its key and the moves that reached it.
+type SearchNode = (Game, StateKey, Vec<(usize, usize)>);
+
+impl Game {
+    pub fn solve(&self) -> Option<Vec<(usize, usize)>> {
+        self.solve_within(usize::MAX)
+    }
+
+    pub fn solve_within(&self, max_states: usize) -> Option<Vec<(usize, usize)>> {
+        match self.breadth_first(max_states).0 {
+            SearchOutcome::Solved(solution) => Some(solution),
+            SearchOutcome::Exhausted | SearchOutcome::GaveUp => None,
+        }
+    }
+
+    // Only a search that explores every reachable board can tell a stage has no solution.
+    pub fn solvability(&self) -> Solvability {
+        match self.breadth_first(SOLVABILITY_SEARCH_LIMIT).0 {
+            SearchOutcome::Solved(_) => Solvability::Solvable,
+            SearchOutcome::Exhausted => Solvability::Unsolvable,
+            SearchOutcome::GaveUp => Solvability::Unknown,
+        }
+    }
+
+    // How the search ended, along with how many boards were expanded on the way.
+    fn breadth_first(&self, max_states: usize) -> (SearchOutcome, usize) {
+        let mut visited: HashSet<StateKey> = HashSet::new();
+        let mut frontier: VecDeque<(Game, Vec<(usize, usize)>)> = VecDeque::new();
+        let mut expansions: usize = 0;
+        visited.insert(self.canonical_key());
+        frontier.push_back((self.clone(), Vec::new()));
+
+        while let Some((game, path)) = frontier.pop_front() {
+            if game.stage_complete() {
+                return (SearchOutcome::Solved(path), expansions);
+            }
+            expansions += 1;
+            for (from, to) in game.legal_moves() {
+                let Some(next) = game.simulate_move(from, to) else {
+                    continue;
+                };
+                if visited.len() >= max_states {
+                    return (SearchOutcome::GaveUp, expansions);
+                }
+                if visited.insert(next.canonical_key()) {
+                    let mut next_path: Vec<(usize, usize)> = path.clone();
+                    next_path.push((from, to));
+                    frontier.push_back((next, next_path));
+                }
+            }
+        }
+        (SearchOutcome::Exhausted, expansions) // No reachable board is sorted.
+    }
+
+    pub fn solve_astar(&self) -> Option<Vec<(usize, usize)>> {
+        self.astar().0
+    }
+
+    // Every board keeps the fewest moves it was reached in, and paths reaching it in as many or
+    // more are pruned. Estimates are counted afresh each time, as that's as cheap as a lookup.
+    fn astar(&self) -> (Option<Vec<(usize, usize)>>, usize) {
+        let start: StateKey = self.canonical_key();
+        let mut nodes: Vec<SearchNode> = vec![(self.clone(), start.clone(), Vec::new())];
+        let mut best_costs: HashMap<StateKey, usize> = HashMap::new();
+        let mut open: BinaryHeap<Reverse<(usize, usize)>> = BinaryHeap::new();
+        let mut expansions: usize = 0;
+        best_costs.insert(start, 0);
+        open.push(Reverse((self.remaining_pours_estimate(), 0)));
+
+        while let Some(Reverse((_, node_ind))) = open.pop() {
+            let (game, key, path) = &nodes[node_ind];
+            if best_costs[key] < path.len() {
+                continue; // A cheaper way to this board was found after this one was queued.
+            }
+            if game.stage_complete() {
+                return (Some(path.clone()), expansions);
+            }
+            expansions += 1;
+            let cost: usize = path.len() + 1;
+            let mut successors: Vec<SearchNode> = Vec::new();
+            for (from, to) in game.legal_moves() {
+                let Some(next) = game.simulate_move(from, to) else {
+                    continue;
+                };
+                let key: StateKey = next.canonical_key();
+                if best_costs.get(&key).is_some_and(|best| *best <= cost) {
+                    continue;
+                }
+                best_costs.insert(key.clone(), cost);
+                let mut next_path: Vec<(usize, usize)> = path.clone();
+                next_path.push((from, to));
+                successors.push((next, key, next_path));
+            }
+            for (next, key, next_path) in successors {
+                let estimate: usize = next.remaining_pours_estimate();
+                open.push(Reverse((cost + estimate, nodes.len())));
+                nodes.push((next, key, next_path));
+            }
+        }
+        (None, expansions)
+    }
+
+    // Iterative deepening: a depth-limited search per bound, so only the boards seen under the
+    // current bound are remembered. Bounds grow one at a time, so the first solution is optimal.
+    pub fn solve_ids(&self, max_depth: usize) -> Option<Vec<(usize, usize)>> {
+        let mut path: Vec<(usize, usize)> = Vec::new();
+        for depth in 0..=max_depth {
+            // Remembers the most moves each board had left to spend when it was searched.
+            let mut visited: HashMap<StateKey, usize> = HashMap::new();
+            if self.search_depth_limited(depth, &mut visited, &mut path) {
+                return Some(path);
+            }
+        }
+        None
+    }
+
+    fn search_depth_limited(
+        &self,
+        budget: usize,
+        visited: &mut HashMap<StateKey, usize>,
+        path: &mut Vec<(usize, usize)>,
+    ) -> bool {
+        if self.stage_complete() {
+            return true;
+        }
+        if budget == 0 {
+            return false;
+        }
+        for (from, to) in self.legal_moves() {
+            let Some(next) = self.simulate_move(from, to) else {
+                continue;
+            };
+            let key: StateKey = next.canonical_key();
+            if visited.get(&key).is_some_and(|spare| *spare >= budget - 1) {
+                continue; // Already searched with at least as many moves to spare.
+            }
+            visited.insert(key, budget - 1);
+            path.push((from, to));
+            if next.search_depth_limited(budget - 1, visited, path) {
+                return true;
+            }
+            path.pop();
+        }
+        false
+    }
+
+    // Counts the runs sitting above each stack's bottom run. Every one of them has to be poured
+    // off its stack at least once, and a pour only ever lifts a single run, so this never
+    // overestimates the pours left and A* still finds a shortest solution. A pour also changes
+    // the count by at most one, so a board is never reached more cheaply after it is expanded.
+    // Counting units instead would overestimate, since one pour can carry several of them.
+    fn remaining_pours_estimate(&self) -> usize {
+        self.stacks
+            .iter()
+            .map(|stack| stack.get_units().len().saturating_sub(1))
+            .sum()
+    }
+
+    // How many moves an optimal solution needs from here, None if none was found.
+    pub fn moves_remaining(&self) -> Option<usize> {
+        self.solve_within(HINT_SEARCH_LIMIT)
+            .map(|solution| solution.len())
+    }
+
+    pub fn hint(&self) -> Option<(usize, usize)> {
+        match self.hint_search() {
+            SearchOutcome::Solved(solution) => solution.first().copied(),
+            SearchOutcome::Exhausted | SearchOutcome::GaveUp => None,
+        }
+    }
+
+    // Kept whole, so a board with no solution can be told apart from one too big to search.
+    pub(crate) fn hint_search(&self) -> SearchOutcome {
+        self.breadth_first(HINT_SEARCH_LIMIT).0
+    }
+
+    // Plays the solution as regular moves, so they can be undone afterwards. `on_frame` is
+    // called after every move and stops the playback by returning false.
+    pub fn auto_solve(&mut self, mut on_frame: impl FnMut(&Game) -> bool) -> AutoSolveOutcome {
+        let Some(solution) = self.solve_within(HINT_SEARCH_LIMIT) else {
+            return AutoSolveOutcome::NoSolution;
+        };
+        self.auto_solved |= !solution.is_empty(); // Even a reset doesn't make the stage new again.
+        for (from, to) in solution {
+            self.move_legally(from, to);
+            if !on_frame(self) && !self.stage_complete() {
+                return AutoSolveOutcome::Stopped;
+            }
+        }
+        AutoSolveOutcome::Solved
+    }
+
+    pub fn compute_par(&mut self) {
+        self.par = self
+            .solve_within(PAR_SEARCH_LIMIT)
+            .map(|solution| solution.len());
+    }
+
+    pub fn get_par(&self) -> Option<usize> {
+        self.par
+    }
+
+    pub fn canonical_key(&self) -> StateKey {
+        let mut stacks: Vec<(usize, Option<KindId>, Vec<Kind>)> = self
+            .stacks
+            .iter()
+            .map(|stack| {
+                (
+                    stack.get_capacity(),
+                    stack.get_lock(),
+                    stack.get_units().to_vec(),
+                )
+            })
+            .collect();
+        stacks.sort();
+        StateKey(stacks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_finds_shortest_sequence() {
+        let game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        let solution: Vec<(usize, usize)> = game.solve().unwrap();
+        assert_eq!(solution.len(), 3);
+
+        let mut replayed: Game = game.clone();
+        for (from, to) in solution {
+            replayed.move_legally(from, to);
+        }
+        assert!(replayed.stage_complete());
+    }
+
+    #[test]
+    fn test_astar_matches_bfs_length() {
+        let stages: Vec<Game> = vec![
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None),
+            Game::new_from_vecs(
+                vec![
+                    vec![1, 2, 1, 0, 0],
+                    vec![2, 1, 0],
+                    vec![2, 0],
+                    vec![0, 0, 0, 0],
+                ],
+                None,
+            ),
+            Game::new_from_vecs(
+                vec![
+                    vec![1, 2, 3, 1],
+                    vec![3, 2, 1, 2],
+                    vec![3, 0, 0, 0],
+                    vec![0, 0, 0, 0],
+                ],
+                None,
+            ),
+        ];
+        for game in stages {
+            let bfs: Vec<(usize, usize)> = game.solve().unwrap();
+            let astar: Vec<(usize, usize)> = game.solve_astar().unwrap();
+            assert_eq!(astar.len(), bfs.len());
+
+            let mut replayed: Game = game.clone();
+            for (from, to) in astar {
+                replayed.move_legally(from, to);
+            }
+            assert!(replayed.stage_complete());
+        }
+
+        let stuck: Game = Game::new_from_vecs(vec![vec![1, 2], vec![2, 1]], None);
+        assert_eq!(stuck.solve_astar(), None);
+    }
+
+    #[test]
+    fn test_astar_expands_fewer_boards_than_bfs() {
+        let game: Game = Game::new_from_vecs(
+            vec![
+                vec![1, 2, 3, 1],
+                vec![3, 2, 1, 2],
+                vec![3, 0, 0, 0],
+                vec![0, 0, 0, 0],
+            ],
+            None,
+        );
+        let (SearchOutcome::Solved(bfs), bfs_expansions) = game.breadth_first(usize::MAX) else {
+            panic!("the stage is solvable");
+        };
+        let (astar, astar_expansions) = game.astar();
+        assert_eq!(astar.unwrap().len(), bfs.len());
+        assert!(astar_expansions < bfs_expansions);
+    }
+
+    #[test]
+    fn test_ids_matches_bfs_length() {
+        let game: Game = Game::new_from_vecs(
+            vec![
+                vec![1, 2, 3, 1],
+                vec![3, 2, 1, 2],
+                vec![3, 0, 0, 0],
+                vec![0, 0, 0, 0],
+            ],
+            None,
+        );
+        let bfs: Vec<(usize, usize)> = game.solve().unwrap();
+        let ids: Vec<(usize, usize)> = game.solve_ids(bfs.len()).unwrap();
+        assert_eq!(ids.len(), bfs.len());
+
+        let mut replayed: Game = game.clone();
+        for (from, to) in ids {
+            replayed.move_legally(from, to);
+        }
+        assert!(replayed.stage_complete());
+
+        assert_eq!(game.solve_ids(bfs.len() - 1), None);
+    }
+
+    #[test]
+    fn test_hint_moves_closer_to_solution() {
+        let game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        let (from, to) = game.hint().unwrap();
+        assert!(game.legal_moves().contains(&(from, to)));
+
+        let distance: usize = game.solve().unwrap().len();
+        let hinted: Game = game.simulate_move(from, to).unwrap();
+        assert_eq!(hinted.solve().unwrap().len(), distance - 1);
+
+        let stuck: Game = Game::new_from_vecs(vec![vec![1, 2], vec![2, 1]], None);
+        assert_eq!(stuck.hint(), None);
+    }
+
+    #[test]
+    fn test_moves_remaining() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        assert_eq!(game.moves_remaining(), game.solve().map(|path| path.len()));
+        let solution: Vec<(usize, usize)> = game.solve().unwrap();
+        for (from, to) in &solution[..solution.len() - 1] {
+            game.move_legally(*from, *to);
+        }
+        assert_eq!(game.moves_remaining(), Some(1));
+
+        let stuck: Game = Game::new_from_vecs(vec![vec![1, 2], vec![2, 1]], None);
+        assert_eq!(stuck.moves_remaining(), None);
+    }
+
+    #[test]
+    fn test_auto_solve_completes_stage() {
+        let mut game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        let mut frames: usize = 0;
+        let outcome: AutoSolveOutcome = game.auto_solve(|_| {
+            frames += 1;
+            true
+        });
+        assert_eq!(outcome, AutoSolveOutcome::Solved);
+        assert!(game.stage_complete());
+        assert_eq!(game.get_move_count(), frames);
+    }
+
+    #[test]
+    fn test_auto_solve_stops_and_can_be_undone() {
+        let start: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        let mut game: Game = start.clone();
+        assert_eq!(game.auto_solve(|_| false), AutoSolveOutcome::Stopped);
+        assert_eq!(game.get_move_count(), 1);
+
+        game.undo_move();
+        assert!(game.stacks == start.stacks);
+
+        let mut stuck: Game = Game::new_from_vecs(vec![vec![1, 2], vec![2, 1]], None);
+        assert_eq!(stuck.auto_solve(|_| true), AutoSolveOutcome::NoSolution);
+    }
+
+    #[test]
+    fn test_par_of_one_move_stage() {
+        let mut game: Game = Game::new_from_vecs(vec![vec![1, 0], vec![1, 0]], None);
+        assert_eq!(game.get_par(), None);
+        game.compute_par();
+        assert_eq!(game.get_par(), Some(1));
+    }
+
+    #[test]
+    fn test_canonical_key_ignores_stack_order() {
+        let game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        let swapped: Game =
+            Game::new_from_vecs(vec![vec![2, 1, 0], vec![0, 0, 0], vec![1, 2, 0]], None);
+        let different: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![1, 2, 0], vec![0, 0, 0]], None);
+
+        assert_eq!(game.canonical_key(), swapped.canonical_key());
+        assert_ne!(game.canonical_key(), different.canonical_key());
+    }
+
+    #[test]
+    fn test_solve_sorted_and_unsolvable_stages() {
+        let sorted: Game = Game::new_from_vecs(vec![vec![1, 1], vec![2, 2]], None);
+        assert_eq!(sorted.solve(), Some(Vec::new()));
+
+        let stuck: Game = Game::new_from_vecs(vec![vec![1, 2], vec![2, 1]], None);
+        assert_eq!(stuck.solve(), None);
+    }
+
+    #[test]
+    fn test_solvability() {
+        let game: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        assert_eq!(game.solvability(), Solvability::Solvable);
+
+        let stuck: Game = Game::new_from_vecs(vec![vec![1, 2], vec![2, 1]], None);
+        assert_eq!(stuck.solvability(), Solvability::Unsolvable);
+    }
+}
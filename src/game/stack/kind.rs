@@ -1,4 +1,9 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 const EMPTY_SLOT_VALUE: KindId = 0;
+pub const EMPTY_SLOT_CHAR: char = '.';
+const LETTERS: usize = 26;
 
 pub type KindId = usize;
 
@@ -22,7 +27,8 @@ impl IsEmpty for KindId {
     }
 }
 
-#[derive(Clone, PartialEq, Eq, Copy, Hash, Ord, PartialOrd)]
+#[derive(Clone, PartialEq, Eq, Copy, Hash, Ord, PartialOrd, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Kind {
     id: KindId,
     quantity: usize,
@@ -59,6 +65,25 @@ impl Kind {
     pub fn get_empty_id() -> KindId {
         EMPTY_SLOT_VALUE
     }
+
+    // 'A'..='Z' stand for ids 1 to 26, 'a'..='z' for 27 to 52 and '.' for an empty slot.
+    pub fn id_from_char(character: char) -> Option<KindId> {
+        match character {
+            EMPTY_SLOT_CHAR => Some(Kind::get_empty_id()),
+            'A'..='Z' => Some(character as usize - 'A' as usize + 1),
+            'a'..='z' => Some(character as usize - 'a' as usize + LETTERS + 1),
+            _ => None,
+        }
+    }
+
+    pub fn char_from_id(id: KindId) -> Option<char> {
+        match id {
+            EMPTY_SLOT_VALUE => Some(EMPTY_SLOT_CHAR),
+            1..=LETTERS => char::from_u32(('A' as usize + id - 1) as u32),
+            _ if id <= 2 * LETTERS => char::from_u32(('a' as usize + id - LETTERS - 1) as u32),
+            _ => None,
+        }
+    }
 }
 
 impl HasId for Kind {
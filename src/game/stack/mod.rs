@@ -1,15 +1,31 @@
 pub mod kind;
 
+use std::fmt;
 use std::iter::FlatMap;
 use std::slice::Iter;
+use std::str::FromStr;
 use std::vec::IntoIter;
 
+use crate::game::error::StackParseError;
 use kind::{Kind, KindId};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
+pub type UnitIds<'a> = FlatMap<Iter<'a, Kind>, IntoIter<KindId>, fn(&Kind) -> IntoIter<KindId>>;
+
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Stack {
     capacity: usize,
     occupancy: usize,
     units: Vec<Kind>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    hidden: usize, // How many units from the bottom are face down, never the top one.
+    #[cfg_attr(feature = "serde", serde(default))]
+    locked_until: Option<KindId>, // Nothing goes in or out until this kind is sorted.
+    #[cfg_attr(feature = "serde", serde(default))]
+    label: Option<String>, // Only shown, the rules never look at it.
 }
 
 impl Stack {
@@ -18,57 +34,70 @@ impl Stack {
             capacity,
             occupancy,
             units,
+            hidden: 0,
+            locked_until: None,
+            label: None,
         }
     }
 
-    pub fn clone(&self) -> Stack {
-        Stack::new(
-            self.get_capacity(),
-            self.get_occupancy(),
-            self.units.iter().map(|unit| unit.clone()).collect(),
-        )
-    }
-
     pub fn new_from_vec(vec: Vec<usize>) -> Stack {
-        let mut units: Vec<Kind> = Vec::new();
-        let mut kind: Kind;
-        let capacity: usize = vec.len();
-        let mut last_unit_id: KindId = Kind::get_empty_id();
-        let mut quantity: usize = 0;
-        let mut occupancy: usize = 0;
-        for (ind, unit_id) in vec.iter().enumerate() {
-            if (*unit_id != last_unit_id) || (ind == capacity - 1) {
-                if last_unit_id != Kind::get_empty_id() {
-                    kind = Kind::new(last_unit_id, quantity);
-                    units.push(kind);
-                    occupancy += quantity;
-                }
-                last_unit_id = *unit_id;
-                quantity = 0;
+        let mut stack: Stack = Stack::new(vec.len(), 0, Vec::new());
+        for unit_id in vec {
+            if unit_id != Kind::get_empty_id() {
+                // Merges into the top run when identical, and always fits since capacity is the length.
+                let _ = stack.push_immigrants(Kind::new(unit_id, 1));
             }
-            quantity += 1;
         }
-        // Push the last kind if it hasn't been pushed yet
-        if last_unit_id != Kind::get_empty_id() {
-            kind = Kind::new(last_unit_id, quantity);
-            units.push(kind);
-            occupancy += quantity;
+        stack
+    }
+
+    pub fn with_capacity(capacity: usize) -> Stack {
+        Stack::new(capacity, 0, Vec::new())
+    }
+
+    // `quantity` units of a single kind, None when they don't fit.
+    pub fn filled(kind: KindId, quantity: usize, capacity: usize) -> Option<Stack> {
+        let mut stack: Stack = Stack::with_capacity(capacity);
+        if quantity > 0 {
+            stack.push_immigrants(Kind::new(kind, quantity)).ok()?;
         }
+        Some(stack)
+    }
 
-        Stack::new(capacity, occupancy, units)
+    // Unlike `new_from_vec`, the capacity is given and the ids are only the units present.
+    pub fn new_with_capacity(capacity: usize, vec: Vec<usize>) -> Option<Stack> {
+        if vec.len() > capacity {
+            return None;
+        }
+        let mut stack: Stack = Stack::new(capacity, 0, Vec::new());
+        for unit_id in vec {
+            stack.push_immigrants(Kind::new(unit_id, 1)).ok()?;
+        }
+        Some(stack)
     }
 
     pub fn is_vacant(&self) -> bool {
         self.get_occupancy() == 0
     }
 
+    pub fn holds_single_kind(&self) -> bool {
+        self.units.len() == 1
+    }
+
+    pub fn is_complete(&self, units_per_kind: &HashMap<KindId, usize>) -> bool {
+        match self.units.as_slice() {
+            [only] => units_per_kind.get(&only.get_id()) == Some(&only.get_quantity()),
+            _ => false,
+        }
+    }
+
     pub fn get_vacancy(&self) -> usize {
         self.get_capacity() - self.get_occupancy()
     }
 
     pub fn clone_top_unit(&self) -> Kind {
         match self.units.last() {
-            Some(top_resident) => top_resident.clone(),
+            Some(top_resident) => *top_resident,
             None => Kind::new_empty(),
         }
     }
@@ -87,35 +116,83 @@ impl Stack {
         }
     }
 
+    // Face-down units of the same kind aren't known to be part of the run, so they stay put.
+    pub fn top_run_len(&self) -> usize {
+        self.get_top_unit_quantity()
+            .min(self.occupancy - self.hidden)
+    }
+
+    // Every unit but the top one is turned face down.
+    pub fn turn_face_down(&mut self) {
+        self.hidden = self.occupancy.saturating_sub(1);
+    }
+
+    pub fn lock_until(&mut self, kind: KindId) {
+        self.locked_until = Some(kind);
+    }
+
+    pub fn get_lock(&self) -> Option<KindId> {
+        self.locked_until
+    }
+
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label = Some(label.into());
+    }
+
+    pub fn get_label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    pub fn get_hidden(&self) -> usize {
+        self.hidden
+    }
+
+    // Cells are counted from the bottom.
+    pub fn is_face_down(&self, cell: usize) -> bool {
+        cell < self.hidden
+    }
+
+    // A pour only ever lifts units from the top run, never the differing kinds beneath it,
+    // so a limit larger than the run is clamped to it.
     pub fn pop_residents_with_limit(&mut self, limit_: Option<usize>) -> Kind {
+        let run: usize = self.top_run_len();
         let top_resident = &mut self.units.last_mut();
-        let quantity: usize;
 
         match top_resident {
             Some(top_resident) => {
-                quantity = limit_.unwrap_or_else(|| top_resident.get_quantity());
+                let quantity: usize = limit_.map_or(run, |limit| limit.min(run));
+                let resident_quantity: usize = top_resident.get_quantity();
 
-                let immigrants: Kind = if quantity < top_resident.get_quantity() {
-                    let new_quantity = top_resident.get_quantity() - quantity;
-                    top_resident.set_quantity(new_quantity);
+                let immigrants: Kind = if quantity < resident_quantity {
+                    top_resident.set_quantity(resident_quantity - quantity);
                     Kind::new(top_resident.get_id(), quantity)
                 } else {
                     self.units.pop().unwrap()
                 };
 
                 self.occupancy = self.occupancy.saturating_sub(immigrants.get_quantity());
+                self.hidden = self.hidden.min(self.occupancy.saturating_sub(1)); // The new top is revealed.
+                self.debug_check_fill();
 
-                return immigrants;
+                immigrants
             }
-            None => return Kind::new_empty(),
+            None => Kind::new_empty(),
         }
     }
 
+    pub fn pop_residents_to_fit(&mut self, vacancy: usize) -> Kind {
+        self.pop_residents_with_limit(Some(self.top_run_len().min(vacancy)))
+    }
+
     pub fn pop_residents(&mut self) -> Kind {
         self.pop_residents_with_limit(None)
     }
 
-    pub fn push_immigrants(&mut self, immigrants: Kind) {
+    // Units that don't fit are handed back untouched.
+    pub fn push_immigrants(&mut self, immigrants: Kind) -> Result<(), Kind> {
+        if immigrants.get_quantity() > self.get_vacancy() {
+            return Err(immigrants);
+        }
         let last_resident = self.units.last_mut();
 
         match last_resident {
@@ -128,11 +205,26 @@ impl Stack {
         }
 
         self.occupancy += immigrants.get_quantity();
+        self.debug_check_fill();
+        Ok(())
+    }
+
+    // Every mutation keeps the occupancy in step with the units, and within the capacity.
+    fn debug_check_fill(&self) {
+        let units: usize = self.units.iter().map(|unit| unit.get_quantity()).sum();
+        debug_assert_eq!(
+            self.occupancy, units,
+            "occupancy out of step with the units"
+        );
+        debug_assert!(
+            self.occupancy <= self.capacity,
+            "{} units overfill a stack of capacity {}",
+            self.occupancy,
+            self.capacity
+        );
     }
 
-    pub fn iter_unit_ids(
-        &self,
-    ) -> FlatMap<Iter<'_, Kind>, IntoIter<KindId>, fn(&Kind) -> IntoIter<KindId>> {
+    pub fn iter_unit_ids(&self) -> UnitIds<'_> {
         fn unit_to_ids(unit: &Kind) -> IntoIter<KindId> {
             vec![unit.get_id(); unit.get_quantity()].into_iter()
         }
@@ -140,6 +232,27 @@ impl Stack {
         self.units.iter().flat_map(unit_to_ids)
     }
 
+    pub fn is_consistent(&self) -> bool {
+        let counted: usize = self.units.iter().map(|unit| unit.get_quantity()).sum();
+        let runs_are_valid: bool = self
+            .units
+            .iter()
+            .all(|unit| !unit.is_empty() && unit.get_quantity() > 0);
+        let runs_are_merged: bool = self
+            .units
+            .windows(2)
+            .all(|pair| pair[0].get_id() != pair[1].get_id());
+        counted == self.occupancy
+            && self.occupancy <= self.capacity
+            && self.hidden <= self.occupancy.saturating_sub(1) // The top always shows.
+            && runs_are_valid
+            && runs_are_merged
+    }
+
+    pub fn get_units(&self) -> &[Kind] {
+        &self.units
+    }
+
     pub fn get_occupancy(&self) -> usize {
         self.occupancy
     }
@@ -148,3 +261,178 @@ impl Stack {
         self.capacity
     }
 }
+
+// Written bottom to top, one character per slot, e.g. "RRG." holds two R units under a G in a
+// stack of capacity 4. Whitespace is ignored.
+impl FromStr for Stack {
+    type Err = StackParseError;
+
+    fn from_str(text: &str) -> Result<Stack, StackParseError> {
+        let mut ids: Vec<usize> = Vec::new();
+        for (position, character) in text.chars().enumerate() {
+            if character.is_whitespace() {
+                continue;
+            }
+            match Kind::id_from_char(character) {
+                Some(id) => ids.push(id),
+                None => {
+                    return Err(StackParseError {
+                        character,
+                        position,
+                    })
+                }
+            }
+        }
+        Ok(Stack::new_from_vec(ids))
+    }
+}
+
+impl fmt::Display for Stack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let vacancies = std::iter::repeat_n(Kind::get_empty_id(), self.get_vacancy());
+        for unit_id in self.iter_unit_ids().chain(vacancies) {
+            write!(f, "{}", Kind::char_from_id(unit_id).unwrap_or('?'))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vacancy_is_capacity_minus_units() {
+        let stack: Stack = Stack::new_with_capacity(5, vec![1, 1, 2]).unwrap();
+        assert_eq!(stack.get_capacity(), 5);
+        assert_eq!(stack.get_vacancy(), 2);
+        assert_eq!(Stack::new_from_vec(vec![3, 0, 0, 0]).get_vacancy(), 3);
+        assert_eq!(
+            Stack::new_with_capacity(2, vec![]).unwrap().get_vacancy(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_push_respects_own_capacity() {
+        let mut short: Stack = Stack::new_with_capacity(2, vec![1]).unwrap();
+        let mut tall: Stack = Stack::new_with_capacity(4, vec![1]).unwrap();
+        let immigrants: Kind = Kind::new(1, 2);
+
+        assert_eq!(short.push_immigrants(immigrants), Err(immigrants));
+        assert_eq!(short.get_occupancy(), 1);
+        assert_eq!(tall.push_immigrants(immigrants), Ok(()));
+        assert_eq!(tall.get_units(), &[Kind::new(1, 3)]);
+        assert_eq!(tall.get_vacancy(), 1);
+    }
+
+    #[test]
+    fn test_only_the_top_run_lifts() {
+        let mut stack: Stack = Stack::new_with_capacity(4, vec![1, 1, 2, 2]).unwrap();
+        assert_eq!(stack.top_run_len(), 2);
+        assert_eq!(stack.pop_residents_with_limit(Some(3)), Kind::new(2, 2));
+        assert_eq!(stack.get_units(), &[Kind::new(1, 2)]);
+        assert_eq!(stack.top_run_len(), 2);
+
+        let mut stack: Stack = Stack::new_with_capacity(4, vec![1, 1, 2, 2]).unwrap();
+        assert_eq!(stack.pop_residents_to_fit(4), Kind::new(2, 2));
+        assert_eq!(stack.get_occupancy(), 2);
+        assert_eq!(Stack::new(3, 0, Vec::new()).top_run_len(), 0);
+    }
+
+    #[test]
+    fn test_pop_residents_to_fit() {
+        let mut stack: Stack = Stack::new_with_capacity(4, vec![2, 1, 1, 1]).unwrap();
+        assert_eq!(stack.pop_residents_to_fit(2), Kind::new(1, 2));
+        assert_eq!(stack.get_units(), &[Kind::new(2, 1), Kind::new(1, 1)]);
+        assert_eq!(stack.pop_residents_to_fit(3), Kind::new(1, 1));
+        assert_eq!(stack.get_occupancy(), 1);
+    }
+
+    #[test]
+    fn test_parse_stack() {
+        let stack: Stack = "RRGB".parse().unwrap();
+        assert_eq!(stack, Stack::new_from_vec(vec![18, 18, 7, 2]));
+        let spaced: Stack = " R R G . \n".parse().unwrap();
+        assert_eq!(spaced, Stack::new_from_vec(vec![18, 18, 7, 0]));
+        assert_eq!(spaced.get_vacancy(), 1);
+        assert_eq!("Az".parse::<Stack>().unwrap().get_top_unit_id(), 52);
+    }
+
+    #[test]
+    fn test_stack_display_round_trips() {
+        for text in ["RRGB", "RG..", "....", "aZz"] {
+            let stack: Stack = text.parse().unwrap();
+            assert_eq!(stack.to_string(), text);
+            assert_eq!(stack.to_string().parse::<Stack>().unwrap(), stack);
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_character() {
+        let error: StackParseError = "RG3B".parse::<Stack>().unwrap_err();
+        assert_eq!(
+            error,
+            StackParseError {
+                character: '3',
+                position: 2
+            }
+        );
+        assert!(error.to_string().contains("'3'"));
+    }
+
+    #[test]
+    fn test_stack_constructors() {
+        let empty: Stack = Stack::with_capacity(3);
+        assert!(empty.is_vacant());
+        assert_eq!(empty.get_vacancy(), 3);
+
+        let full: Stack = Stack::filled(1, 3, 3).unwrap();
+        assert_eq!(full.get_units(), &[Kind::new(1, 3)]);
+        assert_eq!(full.get_vacancy(), 0);
+        assert_eq!(Stack::filled(1, 4, 3), None);
+        assert_eq!(Stack::filled(1, 0, 2), Some(Stack::with_capacity(2)));
+
+        let mut full: Stack = full;
+        let immigrants: Kind = Kind::new(1, 1);
+        assert_eq!(full.push_immigrants(immigrants), Err(immigrants));
+        assert_eq!(full.get_occupancy(), 3);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "overfill")]
+    fn test_overfilled_stack_panics_in_debug() {
+        // Built by hand past its capacity, which the checked constructors never allow.
+        let mut stack: Stack = Stack::new(2, 3, vec![Kind::new(1, 3)]);
+        stack.pop_residents_with_limit(Some(0));
+    }
+
+    #[test]
+    fn test_moving_the_top_reveals_the_unit_beneath() {
+        let mut stack: Stack = Stack::new_with_capacity(4, vec![1, 2, 2, 3]).unwrap();
+        stack.turn_face_down();
+        assert_eq!(stack.get_hidden(), 3);
+        assert!(stack.is_face_down(2));
+        assert!(!stack.is_face_down(3));
+
+        assert_eq!(stack.pop_residents(), Kind::new(3, 1));
+        assert_eq!(stack.get_hidden(), 2);
+        assert!(!stack.is_face_down(2)); // Now on top, so face up.
+        assert_eq!(stack.top_run_len(), 1); // The face-down unit of the same kind stays.
+        assert_eq!(stack.pop_residents(), Kind::new(2, 1));
+        assert_eq!(stack.get_units(), &[Kind::new(1, 1), Kind::new(2, 1)]);
+        assert_eq!(stack.top_run_len(), 1);
+        assert_eq!(stack.get_hidden(), 1);
+
+        assert_eq!(stack.push_immigrants(Kind::new(2, 2)), Ok(())); // Poured on face up.
+        assert_eq!(stack.get_hidden(), 1);
+        assert_eq!(stack.top_run_len(), 3);
+    }
+
+    #[test]
+    fn test_overfilled_stack_is_rejected() {
+        assert_eq!(Stack::new_with_capacity(2, vec![1, 2, 1]), None);
+        assert!(Stack::new_with_capacity(3, vec![1, 2, 1]).is_some());
+    }
+}
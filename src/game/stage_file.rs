@@ -0,0 +1,423 @@
+use crate::game::config;
+use crate::game::error::{StageError, StageParseError};
+use crate::game::stack::kind::{Kind, KindId};
+use crate::game::stack::Stack;
+use crate::game::Game;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const STAGE_FILE_EXTENSION: &str = "stage";
+
+// What a stage's author says about it, none of which changes how it plays.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StageMeta {
+    pub author: Option<String>,
+    pub description: Option<String>,
+    pub par: Option<usize>, // Taken over the solver's own, which big stages may not have.
+}
+
+// A stage file holds optional `key: value` metadata lines, a blank line, then one stack per
+// line in the compact stack encoding, e.g.
+//
+//     name: Warm up
+//     author: Sam
+//     description: Two kinds, one spare stack
+//     par: 4
+//     undos: 3
+//     move_limit: 12
+//     lock: 3 R
+//     label: 3 Sort here
+//
+//     RG..
+//     GR..
+//     ....
+//
+// Without a blank line the whole file is read as stacks. A lock keeps the numbered stack shut
+// until the units of the given kind are sorted, a label names the numbered stack on the board.
+impl Game {
+    pub fn parse_stage(text: &str) -> Result<Game, StageParseError> {
+        let lines: Vec<&str> = text.lines().collect();
+        let (metadata, stacks): (&[&str], &[&str]) =
+            match lines.iter().position(|line| line.trim().is_empty()) {
+                Some(blank) => (&lines[..blank], &lines[blank + 1..]),
+                None => (&[], &lines[..]),
+            };
+        let first_stack_line: usize = lines.len() - stacks.len() + 1;
+
+        let mut stage_name: Option<String> = None;
+        let mut meta: StageMeta = StageMeta::default();
+        let mut undo_limit: Option<usize> = None;
+        let mut move_limit: Option<usize> = None;
+        let mut locks: Vec<(usize, usize, KindId)> = Vec::new(); // Line, stack and kind.
+        let mut labels: Vec<(usize, usize, String)> = Vec::new(); // Line, stack and label.
+        for (ind, line) in metadata.iter().enumerate() {
+            let Some((key, value)) = line.split_once(':') else {
+                return Err(StageParseError::Metadata {
+                    line: ind + 1,
+                    text: line.to_string(),
+                });
+            };
+            match key.trim() {
+                "name" => stage_name = Some(value.trim().to_string()),
+                "author" => meta.author = Some(value.trim().to_string()),
+                "description" => meta.description = Some(value.trim().to_string()),
+                "undos" | "move_limit" | "par" => {
+                    let Ok(limit) = value.trim().parse::<usize>() else {
+                        return Err(StageParseError::Metadata {
+                            line: ind + 1,
+                            text: line.to_string(),
+                        });
+                    };
+                    match key.trim() {
+                        "undos" => undo_limit = Some(limit),
+                        "par" => meta.par = Some(limit),
+                        _ => move_limit = Some(limit),
+                    }
+                }
+                // "lock: 3 B" keeps stack 3 shut until the B units are sorted.
+                "lock" => {
+                    let lock: Option<(usize, KindId)> = Game::parse_lock(value);
+                    match lock {
+                        Some((stack, kind)) => locks.push((ind + 1, stack, kind)),
+                        None => {
+                            return Err(StageParseError::Metadata {
+                                line: ind + 1,
+                                text: line.to_string(),
+                            })
+                        }
+                    }
+                }
+                "label" => match Game::parse_label(value) {
+                    Some((stack, label)) => labels.push((ind + 1, stack, label)),
+                    None => {
+                        return Err(StageParseError::Metadata {
+                            line: ind + 1,
+                            text: line.to_string(),
+                        })
+                    }
+                },
+                _ => {} // Other keys are left for later versions of the format.
+            }
+        }
+
+        let mut parsed: Vec<Stack> = Vec::new();
+        for (ind, line) in stacks.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match line.parse::<Stack>() {
+                Ok(stack) => parsed.push(stack),
+                Err(error) => {
+                    return Err(StageParseError::Stack {
+                        line: first_stack_line + ind,
+                        error,
+                    })
+                }
+            }
+        }
+        if parsed.is_empty() {
+            return Err(StageParseError::NoStacks);
+        }
+        if let Some((kind, count)) = Game::unsortable_kind(&parsed) {
+            return Err(StageParseError::Unsortable { kind, count });
+        }
+        let units_per_kind: HashMap<KindId, usize> = Game::count_kinds(&parsed);
+        for (line, stack, kind) in locks {
+            match parsed.get_mut(stack) {
+                Some(locked) if units_per_kind.contains_key(&kind) => locked.lock_until(kind),
+                _ => {
+                    return Err(StageParseError::Metadata {
+                        line,
+                        text: metadata[line - 1].to_string(),
+                    })
+                }
+            }
+        }
+        for (line, stack, label) in labels {
+            match parsed.get_mut(stack) {
+                Some(labeled) => labeled.set_label(label),
+                None => {
+                    return Err(StageParseError::Metadata {
+                        line,
+                        text: metadata[line - 1].to_string(),
+                    })
+                }
+            }
+        }
+        let mut stage: Game = Game::new(parsed, stage_name);
+        if stage.is_stuck() {
+            return Err(StageParseError::Stuck);
+        }
+        stage.set_undo_limit(undo_limit);
+        stage.set_move_limit(move_limit);
+        stage.set_meta(meta);
+        Ok(stage)
+    }
+
+    fn parse_lock(value: &str) -> Option<(usize, KindId)> {
+        let (stack, kind) = value.trim().split_once(char::is_whitespace)?;
+        let stack: usize = stack.parse::<usize>().ok()?.checked_sub(1)?;
+        let mut chars = kind.trim().chars();
+        match (chars.next(), chars.next()) {
+            (Some(kind), None) => Some((stack, Kind::id_from_char(kind)?)),
+            _ => None,
+        }
+    }
+
+    fn parse_label(value: &str) -> Option<(usize, String)> {
+        let (stack, label) = value.trim().split_once(char::is_whitespace)?;
+        let stack: usize = stack.parse::<usize>().ok()?.checked_sub(1)?;
+        Some((stack, label.trim().to_string()))
+    }
+
+    // Files that can't be read or parsed are skipped with a warning, only an unreadable
+    // directory fails the whole load. Unnamed stages are named after their file.
+    pub fn load_stages_from_dir(path: &Path) -> Result<Vec<Game>, StageError> {
+        let mut files: Vec<PathBuf> = Vec::new();
+        for dir_entry in fs::read_dir(path)? {
+            let file: PathBuf = dir_entry?.path();
+            if file
+                .extension()
+                .is_some_and(|ext| ext == STAGE_FILE_EXTENSION)
+            {
+                files.push(file);
+            }
+        }
+        files.sort();
+
+        let mut stages: Vec<Game> = Vec::new();
+        for file in files {
+            match Game::load_stage_file(&file) {
+                Ok(stage) => stages.push(stage),
+                Err(error) => eprintln!("Skipping {}", error),
+            }
+        }
+        Ok(stages)
+    }
+
+    pub fn load_stage_file(file: &Path) -> Result<Game, StageError> {
+        let text: String = fs::read_to_string(file).map_err(|error| StageError::Read {
+            file: file.to_path_buf(),
+            error,
+        })?;
+        let mut stage: Game = Game::parse_stage(&text).map_err(|error| StageError::Parse {
+            file: file.to_path_buf(),
+            error,
+        })?;
+        if stage.stage_name.is_empty() {
+            let stem = file.file_stem().unwrap_or_default();
+            stage.stage_name = stem.to_string_lossy().to_string();
+        }
+        Ok(stage)
+    }
+
+    pub fn user_or_builtin_stages() -> Vec<Game> {
+        if let Some(dir) = config::stages_dir().filter(|dir| dir.is_dir()) {
+            match Game::load_stages_from_dir(&dir) {
+                Ok(stages) if !stages.is_empty() => return stages,
+                Ok(_) => {}
+                Err(error) => eprintln!("{}", error),
+            }
+        }
+        Game::get_stages()
+    }
+
+    // A stage is only complete once every kind sits in a stack of its own, so each kind needs a
+    // distinct stack with room for all of its units. Handing the largest kinds the tallest
+    // stacks finds such an assignment whenever there is one.
+    pub(crate) fn unsortable_kind(stacks: &[Stack]) -> Option<(char, usize)> {
+        let units_per_kind: HashMap<KindId, usize> = Game::count_kinds(stacks);
+        let mut kinds: Vec<(usize, KindId)> = units_per_kind
+            .iter()
+            .map(|(kind, count)| (*count, *kind))
+            .collect();
+        kinds.sort_by(|a, b| b.cmp(a));
+        let mut capacities: Vec<usize> = stacks.iter().map(|stack| stack.get_capacity()).collect();
+        capacities.sort_by(|a, b| b.cmp(a));
+
+        for (ind, (count, kind)) in kinds.into_iter().enumerate() {
+            if capacities.get(ind).is_none_or(|capacity| *capacity < count) {
+                return Some((Kind::char_from_id(kind).unwrap_or('?'), count));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::error::StackParseError;
+
+    #[test]
+    fn test_parse_valid_stage() {
+        let game: Game = Game::parse_stage("name: Warm up\n\nRG..\nGR..\n....\n").unwrap();
+        assert_eq!(game.stage_name, "Warm up");
+        assert_eq!(game.stacks.len(), 3);
+        assert_eq!(game.stacks[0].to_string(), "RG..");
+        assert!(game.stacks[2].is_vacant());
+        assert!(game.solve().is_some());
+
+        let unnamed: Game = Game::parse_stage("RR\nGG").unwrap();
+        assert_eq!(unnamed.stage_name, "");
+        assert!(unnamed.stage_complete());
+        assert_eq!(unnamed.undos_left(), None);
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        let game: Game =
+            Game::parse_stage("undos: 3\nmove_limit: 9\n\nRG..\nGR..\n....\n").unwrap();
+        assert_eq!(game.undos_left(), Some(3));
+        assert_eq!(game.get_move_limit(), Some(9));
+        assert!(matches!(
+            Game::parse_stage("undos: many\n\nRG..\nGR..\n"),
+            Err(StageParseError::Metadata { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_stage_meta() {
+        let game: Game = Game::parse_stage(
+            "name: Warm up
+author: Sam
+description: Two kinds, one spare stack
+par: 4
+
+GR.
+RG.
+...
+",
+        )
+        .unwrap();
+        let meta: StageMeta = StageMeta {
+            author: Some("Sam".to_string()),
+            description: Some("Two kinds, one spare stack".to_string()),
+            par: Some(4),
+        };
+        assert_eq!(game.get_meta(), &meta);
+        #[cfg(feature = "serde")]
+        {
+            let json: String = serde_json::to_string(&game).unwrap();
+            let restored: Game = serde_json::from_str(&json).unwrap();
+            assert_eq!(restored.get_meta(), &meta);
+        }
+        assert!(matches!(
+            Game::parse_stage("par: soon\n\nGR.\nRG.\n...\n"),
+            Err(StageParseError::Metadata { line: 1, .. })
+        ));
+        let bare: Game = Game::parse_stage("GR.\nRG.\n...\n").unwrap();
+        assert_eq!(bare.get_meta(), &StageMeta::default());
+    }
+
+    #[test]
+    fn test_parse_locks() {
+        let game: Game = Game::parse_stage(
+            "lock: 3 R
+
+GR.
+RG.
+...
+...
+",
+        )
+        .unwrap();
+        assert_eq!(game.get_stacks()[2].get_lock(), Kind::id_from_char('R'));
+        assert!(game.is_locked(2));
+        assert!(game.solve().is_some());
+        for text in ["lock: 4 R", "lock: 3 B", "lock: 3", "lock: x R"] {
+            assert!(matches!(
+                Game::parse_stage(&format!("{}\n\nGR.\nRG.\n...\n", text)),
+                Err(StageParseError::Metadata { line: 1, .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_labels() {
+        let game: Game = Game::parse_stage(
+            "label: 3 Sort here
+
+GR.
+RG.
+...
+",
+        )
+        .unwrap();
+        assert_eq!(game.get_stacks()[2].get_label(), Some("Sort here"));
+        assert_eq!(game.get_stacks()[0].get_label(), None);
+        for text in ["label: 4 Sort here", "label: 3", "label: x Sort here"] {
+            assert!(matches!(
+                Game::parse_stage(&format!("{}\n\nGR.\nRG.\n...\n", text)),
+                Err(StageParseError::Metadata { line: 1, .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_stage_that_cannot_be_sorted() {
+        let result: Result<Game, StageParseError> = Game::parse_stage("\nRRG\nGRR\n...\n");
+        assert_eq!(
+            result.err(),
+            Some(StageParseError::Unsortable {
+                kind: 'R',
+                count: 4
+            })
+        );
+        // The only empty stack stays locked until the Rs are sorted, which needs it first.
+        assert_eq!(
+            Game::parse_stage("lock: 3 R\n\nGR.\nRG.\n...\n").err(),
+            Some(StageParseError::Stuck)
+        );
+    }
+
+    #[test]
+    fn test_load_stages_from_dir() {
+        let dir: PathBuf =
+            std::env::temp_dir().join(format!("sorting-game-stages-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("02-second.stage"), "name: Second\n\nRG\nGR\n..\n").unwrap();
+        fs::write(dir.join("01-first.stage"), "RR.\nGG.\n").unwrap();
+        fs::write(dir.join("03-broken.stage"), "R#\n").unwrap();
+        fs::write(dir.join("notes.txt"), "not a stage").unwrap();
+
+        let stages: Vec<Game> = Game::load_stages_from_dir(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        let names: Vec<&str> = stages
+            .iter()
+            .map(|stage| stage.stage_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["01-first", "Second"]);
+
+        assert!(Game::load_stages_from_dir(&dir).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors_point_at_the_line() {
+        assert_eq!(
+            Game::parse_stage("name: Broken\n\nRG..\nG#..").err(),
+            Some(StageParseError::Stack {
+                line: 4,
+                error: StackParseError {
+                    character: '#',
+                    position: 1
+                }
+            })
+        );
+        assert_eq!(
+            Game::parse_stage("nameless\n\nRR").err(),
+            Some(StageParseError::Metadata {
+                line: 1,
+                text: "nameless".to_string()
+            })
+        );
+        assert_eq!(
+            Game::parse_stage("name: Empty\n\n").err(),
+            Some(StageParseError::NoStacks)
+        );
+    }
+}
@@ -1,5 +1,8 @@
+use crate::game::solver::Solvability;
 use crate::game::stack::Stack;
 use crate::game::Game;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
 
 impl Game {
     fn vecs_to_stacks(vecs: Vec<Vec<usize>>) -> Vec<Stack> {
@@ -12,7 +15,7 @@ impl Game {
         stacks
     }
 
-    fn new_from_vecs(vecs: Vec<Vec<usize>>, stage_name: Option<String>) -> Game {
+    pub fn new_from_vecs(vecs: Vec<Vec<usize>>, stage_name: Option<String>) -> Game {
         Game::new(Game::vecs_to_stacks(vecs), stage_name)
     }
 
@@ -50,6 +53,13 @@ impl Game {
                 vec![2, 5, 1, 10, 0],
                 vec![5, 2, 5],
             ],
+            // Tall and short tubes mixed together.
+            vec![
+                vec![1, 2, 1, 0, 0],
+                vec![2, 1, 0],
+                vec![2, 0],
+                vec![0, 0, 0, 0],
+            ],
         ];
         let mut stages: Vec<Game> = Vec::new();
         for (ind, vec_stacks) in stage_vec.iter().enumerate() {
@@ -58,4 +68,141 @@ impl Game {
         }
         stages
     }
+
+    // The built-in campaign from easy to hard.
+    pub fn get_stages_sorted() -> Vec<Game> {
+        Game::sort_by_difficulty(Game::get_stages())
+    }
+
+    // Equally rated stages keep their order. The ratings are kept by the returned stages.
+    pub fn sort_by_difficulty(mut stages: Vec<Game>) -> Vec<Game> {
+        Game::check_each(&stages, |stage| {
+            stage.difficulty();
+        });
+        stages.sort_by_key(|stage| stage.difficulty());
+        stages
+    }
+
+    // Stages the solver can't settle either way are kept, they may well be solvable.
+    pub fn without_unsolvable(stages: Vec<Game>) -> Vec<Game> {
+        let solvability: Vec<Solvability> = Game::check_each(&stages, |stage| stage.solvability());
+        stages
+            .into_iter()
+            .zip(solvability)
+            .filter_map(|(stage, solvability)| match solvability {
+                Solvability::Unsolvable => {
+                    eprintln!("'{}' can't be sorted, it was left out", stage.stage_name);
+                    None
+                }
+                _ => Some(stage),
+            })
+            .collect()
+    }
+
+    // Every check is a search of its own, so they're shared out among one worker per core,
+    // each taking the next unchecked stage. The results come back in the stages' order.
+    fn check_each<T: Send>(stages: &[Game], check: impl Fn(&Game) -> T + Sync) -> Vec<T> {
+        let workers: usize = thread::available_parallelism()
+            .map_or(1, |cores| cores.get())
+            .min(stages.len());
+        let next: AtomicUsize = AtomicUsize::new(0);
+        let (next, check): (&AtomicUsize, &(dyn Fn(&Game) -> T + Sync)) = (&next, &check);
+        let mut checked: Vec<(usize, T)> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(move || {
+                        let mut checked: Vec<(usize, T)> = Vec::new();
+                        loop {
+                            let ind: usize = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(stage) = stages.get(ind) else {
+                                break checked;
+                            };
+                            checked.push((ind, check(stage)));
+                        }
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("the solver doesn't panic"))
+                .collect()
+        });
+        checked.sort_by_key(|(ind, _)| *ind);
+        checked.into_iter().map(|(_, result)| result).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::error::MoveError;
+
+    #[test]
+    fn test_mixed_capacity_stage_is_solvable() {
+        let stages: Vec<Game> = Game::get_stages();
+        let mixed: &Game = stages.last().unwrap();
+        let capacities: Vec<usize> = mixed
+            .stacks
+            .iter()
+            .map(|stack| stack.get_capacity())
+            .collect();
+        assert_eq!(capacities, vec![5, 3, 2, 4]);
+        assert!(mixed.solve().is_some());
+    }
+
+    #[test]
+    fn test_sorted_stages_get_harder() {
+        let stages: Vec<Game> = Game::get_stages_sorted();
+        assert_eq!(stages.len(), Game::get_stages().len());
+        assert!(stages
+            .windows(2)
+            .all(|pair| pair[0].difficulty() <= pair[1].difficulty()));
+    }
+
+    #[test]
+    fn test_unsolvable_stages_are_left_out() {
+        let stages: Vec<Game> = vec![
+            Game::new_from_vecs(vec![vec![1, 2], vec![2, 1]], Some("Stuck".to_string())),
+            Game::new_from_vecs(
+                vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]],
+                Some("Fine".to_string()),
+            ),
+        ];
+        let kept: Vec<Game> = Game::without_unsolvable(stages);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].get_stage_name(), "Fine");
+    }
+
+    #[test]
+    fn test_checks_come_back_in_stage_order() {
+        let stages: Vec<Game> = (0..40)
+            .map(|ind| Game::new_from_vecs(vec![vec![1, 0], vec![1, 0]], Some(ind.to_string())))
+            .collect();
+        let names: Vec<String> = Game::check_each(&stages, |stage| stage.stage_name.clone());
+        let expected: Vec<String> = (0..40).map(|ind: usize| ind.to_string()).collect();
+        assert_eq!(names, expected);
+        assert!(Game::check_each(&[], |stage| stage.solvability()).is_empty());
+    }
+
+    #[test]
+    fn test_pour_judged_by_target_capacity() {
+        let game: Game = Game::new_from_vecs(
+            vec![
+                vec![1, 1, 1, 0],
+                vec![0, 0, 0, 0, 0],
+                vec![1, 0],
+                vec![1, 1],
+            ],
+            None,
+        );
+        let tall: Game = game.simulate_move(0, 1).unwrap();
+        assert!(tall.stacks[0].is_vacant());
+        assert_eq!(tall.stacks[1].get_occupancy(), 3);
+
+        let short: Game = game.simulate_move(0, 2).unwrap();
+        assert_eq!(short.stacks[0].get_occupancy(), 2);
+        assert_eq!(short.stacks[2].get_vacancy(), 0);
+
+        assert_eq!(game.check_move(0, 3), Err(MoveError::NoRoom));
+    }
 }
@@ -0,0 +1,102 @@
+use crate::game::session::LoopOutcome;
+use crate::game::style::RenderStyle;
+use crate::game::Game;
+use std::time::Duration;
+
+// Totals over every stage played in one sitting, finished or not.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct SessionStats {
+    pub stages_played: usize,
+    pub stages_completed: usize,
+    pub total_moves: usize,
+    pub total_undos: usize,
+    pub total_time: Duration,
+    efficiencies: Vec<usize>, // One per completed stage that has a par.
+}
+
+impl SessionStats {
+    pub fn record(&mut self, stage: &Game, outcome: LoopOutcome) {
+        self.stages_played += 1;
+        self.total_moves += stage.get_move_count();
+        self.total_undos += stage.get_undo_count();
+        self.total_time += stage.get_elapsed();
+        if matches!(outcome, LoopOutcome::Completed | LoopOutcome::Skipped) {
+            self.stages_completed += 1;
+        }
+        if outcome == LoopOutcome::Completed {
+            if let Some(par) = stage.get_par() {
+                let efficiency: usize = Game::efficiency_percent(stage.get_move_count(), par);
+                self.efficiencies.push(efficiency);
+            }
+        }
+    }
+
+    pub fn average_efficiency(&self) -> Option<usize> {
+        match self.efficiencies.len() {
+            0 => None,
+            count => Some(self.efficiencies.iter().sum::<usize>() / count),
+        }
+    }
+
+    pub fn summary(&self, style: RenderStyle) -> String {
+        let efficiency: String = match self.average_efficiency() {
+            Some(efficiency) => format!("{}%", efficiency),
+            None => style.dash().to_string(),
+        };
+        [
+            "Session summary:".to_string(),
+            format!(
+                "  stages completed: {} / {}",
+                self.stages_completed, self.stages_played
+            ),
+            format!("  moves: {}", self.total_moves),
+            format!("  undos: {}", self.total_undos),
+            format!("  time: {}", Game::format_elapsed(self.total_time)),
+            format!("  average efficiency: {}", efficiency),
+        ]
+        .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_totals_over_two_stages() {
+        let mut stats: SessionStats = SessionStats::default();
+
+        let mut first: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        first.compute_par();
+        first.run_script(&[(0, 2), (1, 0), (1, 2)]).unwrap();
+        stats.record(&first, LoopOutcome::Completed);
+
+        let mut second: Game =
+            Game::new_from_vecs(vec![vec![1, 0], vec![1, 0], vec![0, 0, 0]], None);
+        second.compute_par();
+        second.run_script(&[(0, 2)]).unwrap();
+        second.undo_move();
+        second.run_script(&[(0, 1)]).unwrap();
+        stats.record(&second, LoopOutcome::Completed);
+
+        assert_eq!(stats.stages_played, 2);
+        assert_eq!(stats.stages_completed, 2);
+        assert_eq!(stats.total_moves, 4);
+        assert_eq!(stats.total_undos, 1);
+        assert_eq!(stats.average_efficiency(), Some(100));
+        assert!(stats.summary(RenderStyle::Ascii).contains("moves: 4"));
+    }
+
+    #[test]
+    fn test_skipped_stage_counts_as_done_without_efficiency() {
+        let mut stats: SessionStats = SessionStats::default();
+        let mut skipped: Game =
+            Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+        skipped.compute_par();
+        stats.record(&skipped, LoopOutcome::Skipped);
+        assert_eq!(stats.stages_played, 1);
+        assert_eq!(stats.stages_completed, 1);
+        assert_eq!(stats.average_efficiency(), None);
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+const BITS_PER_WORD: usize = u64::BITS as usize;
+
+#[derive(Clone, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KindStatus {
+    words: Vec<u64>,
+}
+
+// Clearing bits leaves zeroed words behind, which mustn't tell equal statuses apart.
+impl PartialEq for KindStatus {
+    fn eq(&self, other: &KindStatus) -> bool {
+        let significant = |words: &[u64]| -> usize {
+            words
+                .iter()
+                .rposition(|word| *word != 0)
+                .map_or(0, |last| last + 1)
+        };
+        self.words[..significant(&self.words)] == other.words[..significant(&other.words)]
+    }
+}
+
+impl KindStatus {
+    pub fn new() -> KindStatus {
+        KindStatus { words: Vec::new() }
+    }
+
+    pub fn set_bit(&mut self, index: usize) {
+        let word: usize = index / BITS_PER_WORD;
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << (index % BITS_PER_WORD);
+    }
+
+    pub fn clear_bit(&mut self, index: usize) {
+        if let Some(word) = self.words.get_mut(index / BITS_PER_WORD) {
+            *word &= !(1 << (index % BITS_PER_WORD));
+        }
+    }
+
+    pub fn is_set(&self, index: usize) -> bool {
+        match self.words.get(index / BITS_PER_WORD) {
+            Some(word) => word & (1 << (index % BITS_PER_WORD)) != 0,
+            None => false,
+        }
+    }
+
+    pub fn all_set(&self, count: usize) -> bool {
+        (0..count).all(|index| self.is_set(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bits_beyond_one_word() {
+        let mut status: KindStatus = KindStatus::new();
+        for index in 0..70 {
+            status.set_bit(index);
+        }
+        assert!(status.all_set(70));
+        assert!(!status.all_set(71));
+
+        status.clear_bit(65);
+        assert!(!status.is_set(65));
+        assert!(status.is_set(64));
+        assert!(!status.all_set(70));
+    }
+
+    #[test]
+    fn test_cleared_bits_compare_equal_to_unset() {
+        let mut status: KindStatus = KindStatus::new();
+        status.set_bit(70);
+        assert_ne!(status, KindStatus::new());
+        status.clear_bit(70);
+        assert_eq!(status, KindStatus::new());
+    }
+}
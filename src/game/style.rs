@@ -0,0 +1,152 @@
+use crate::game::cost::CostModel;
+use crate::game::keys::KeyBindings;
+use crate::game::palette::Palette;
+use std::env;
+use std::sync::Arc;
+
+const NO_COLOR_VAR: &str = "NO_COLOR";
+const PROGRESS_BAR_WIDTH: usize = 10;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum RenderStyle {
+    #[default]
+    Color,
+    // Plain ASCII for limited terminals: letters for kinds and no escape codes.
+    Ascii,
+}
+
+// How a stack with nothing in it is drawn. Dotted slots and an "empty" tag make it easy to spot
+// as a target, plain draws its slots like those of any other stack.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum EmptyStackLook {
+    #[default]
+    Dotted,
+    Plain,
+}
+
+impl RenderStyle {
+    // Per no-color.org, a non-empty NO_COLOR turns colors off whatever style was asked for.
+    pub fn honoring_no_color(self) -> RenderStyle {
+        let no_color: bool = env::var_os(NO_COLOR_VAR).is_some_and(|value| !value.is_empty());
+        match no_color {
+            true => RenderStyle::Ascii,
+            false => self,
+        }
+    }
+
+    pub fn complete_marker(self) -> &'static str {
+        match self {
+            RenderStyle::Color => " ✓",
+            RenderStyle::Ascii => " *",
+        }
+    }
+
+    // Follows a unit drawn once for a run of them, e.g. the 4 in "R×4".
+    pub fn run_count(self, count: usize) -> String {
+        match (count, self) {
+            (0 | 1, _) => "".to_string(),
+            (count, RenderStyle::Color) => format!("×{}", count),
+            (count, RenderStyle::Ascii) => format!("x{}", count),
+        }
+    }
+
+    pub fn lock_marker(self) -> &'static str {
+        match self {
+            RenderStyle::Color => " 🔒",
+            RenderStyle::Ascii => " [locked]",
+        }
+    }
+
+    pub fn dotted_slot(self) -> &'static str {
+        match self {
+            RenderStyle::Color => "┄┄",
+            RenderStyle::Ascii => ":",
+        }
+    }
+
+    pub fn dash(self) -> &'static str {
+        match self {
+            RenderStyle::Color => "—",
+            RenderStyle::Ascii => "-",
+        }
+    }
+
+    pub fn arrow(self) -> &'static str {
+        match self {
+            RenderStyle::Color => "→",
+            RenderStyle::Ascii => "->",
+        }
+    }
+
+    pub fn times(self) -> &'static str {
+        match self {
+            RenderStyle::Color => "×",
+            RenderStyle::Ascii => "x",
+        }
+    }
+
+    pub fn stars(self, filled: usize, total: usize) -> String {
+        let (full, empty): (&str, &str) = match self {
+            RenderStyle::Color => ("★", "☆"),
+            RenderStyle::Ascii => ("*", "."),
+        };
+        format!("{}{}", full.repeat(filled), empty.repeat(total - filled))
+    }
+
+    pub fn progress_bar(self, completed: usize, total: usize) -> String {
+        let (full, empty): (&str, &str) = match self {
+            RenderStyle::Color => ("█", "░"),
+            RenderStyle::Ascii => ("#", "."),
+        };
+        let filled: usize = match total {
+            0 => PROGRESS_BAR_WIDTH,
+            _ => completed.min(total) * PROGRESS_BAR_WIDTH / total,
+        };
+        format!(
+            "[{}{}]",
+            full.repeat(filled),
+            empty.repeat(PROGRESS_BAR_WIDTH - filled)
+        )
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            RenderStyle::Color => "|**************|\n| Sorting Game |\n****************",
+            RenderStyle::Ascii => "+--------------+\n| Sorting Game |\n+--------------+",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Settings {
+    pub style: RenderStyle,
+    pub palette: Arc<Palette>,
+    pub explicit_moves: bool, // Always ask for the target, even when only one is legal.
+    pub keys: Arc<KeyBindings>,
+    pub undo_limit: Option<usize>, // For stages that don't set their own.
+    pub quiet: bool,
+    pub two_player: bool,
+    pub no_sort: bool, // Keep the stages in their own order, rather than easy to hard.
+    pub check_solvable: bool, // Leave out stages the solver proves can't be sorted.
+    pub cost_model: CostModel,
+    pub empty_look: EmptyStackLook,
+    pub fast_forward: bool, // Play the moves that leave no choice without asking.
+    pub compact_runs: bool, // Draw a run of identical units once, with its length.
+    pub face_down: bool,    // Every unit under the top of its stack starts out hidden.
+    pub fast: bool,         // Straight into each stage, without its intro screen.
+    pub dev: bool,          // Typing "skip" passes over a stage without scoring it.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_bar() {
+        assert_eq!(RenderStyle::Ascii.progress_bar(0, 10), "[..........]");
+        assert_eq!(RenderStyle::Ascii.progress_bar(5, 10), "[#####.....]");
+        assert_eq!(RenderStyle::Ascii.progress_bar(10, 10), "[##########]");
+        assert_eq!(RenderStyle::Ascii.progress_bar(1, 3), "[###.......]");
+        assert_eq!(RenderStyle::Color.progress_bar(5, 10), "[█████░░░░░]");
+    }
+}
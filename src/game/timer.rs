@@ -0,0 +1,115 @@
+use crate::game::Game;
+use std::time::{Duration, Instant};
+
+// Time spent playing a stage. It only runs between `start` and `pause`, so stepping away
+// doesn't count against the player.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct StageTimer {
+    running_since: Option<Instant>,
+    banked: Duration,
+    started: bool,
+}
+
+impl StageTimer {
+    pub fn start(&mut self) {
+        if self.running_since.is_none() {
+            self.running_since = Some(Instant::now());
+            self.started = true;
+        }
+    }
+
+    pub fn pause(&mut self) {
+        if let Some(since) = self.running_since.take() {
+            self.banked += since.elapsed();
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running_since.is_some()
+    }
+
+    pub fn has_started(&self) -> bool {
+        self.started
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.banked
+            + self
+                .running_since
+                .map_or(Duration::ZERO, |since| since.elapsed())
+    }
+}
+
+impl Game {
+    pub fn elapsed_since(start: Instant) -> String {
+        Game::format_elapsed(start.elapsed())
+    }
+
+    // Minutes and seconds, with hours in front once there are any.
+    pub fn format_elapsed(elapsed: Duration) -> String {
+        let seconds: u64 = elapsed.as_secs();
+        match seconds / 3600 {
+            0 => format!("{}:{:02}", seconds / 60, seconds % 60),
+            hours => format!("{}:{:02}:{:02}", hours, seconds / 60 % 60, seconds % 60),
+        }
+    }
+
+    pub fn pause(&mut self) {
+        self.timer.pause();
+    }
+
+    pub fn resume(&mut self) {
+        self.timer.start();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.timer.has_started() && !self.timer.is_running()
+    }
+
+    pub fn get_elapsed(&self) -> Duration {
+        self.timer.elapsed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_elapsed_formatting() {
+        assert_eq!(Game::format_elapsed(Duration::from_millis(999)), "0:00");
+        assert_eq!(Game::format_elapsed(Duration::from_secs(75)), "1:15");
+        assert_eq!(
+            Game::format_elapsed(Duration::from_secs(3600 + 62)),
+            "1:01:02"
+        );
+        let start: Instant = Instant::now() - Duration::from_secs(125);
+        assert_eq!(Game::elapsed_since(start), "2:05");
+    }
+
+    #[test]
+    fn test_paused_timer_stands_still() {
+        let mut timer: StageTimer = StageTimer::default();
+        assert!(!timer.has_started());
+        timer.start();
+        timer.pause();
+        let paused_at: Duration = timer.elapsed();
+        std::thread::sleep(Duration::from_millis(5));
+        assert_eq!(timer.elapsed(), paused_at);
+        assert!(timer.has_started());
+    }
+
+    #[test]
+    fn test_pausing_does_not_take_a_turn() {
+        let mut game: Game = Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 0, 0]], None);
+        game.move_legally(0, 1);
+        game.resume();
+        let turn: usize = game.get_turn();
+        game.pause();
+        assert!(game.is_paused());
+        game.resume();
+        assert!(!game.is_paused());
+        assert_eq!(game.get_turn(), turn);
+        assert_eq!(game.get_move_count(), 1);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::game::Game;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TutorialStep {
+    from: usize,
+    to: usize,
+    select_prompt: &'static str,
+    pour_prompt: &'static str,
+}
+
+// The intended moves, in order. Only the move a step asks for is let through, so the step
+// is simply how many moves were made, and undo (or a reset) steps back with them.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Tutorial {
+    steps: Vec<TutorialStep>,
+}
+
+impl Game {
+    pub fn tutorial() -> Game {
+        let mut game: Game = Game::new_from_vecs(
+            vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]],
+            Some("Tutorial".to_string()),
+        );
+        let step = |from: usize, to: usize, select_prompt, pour_prompt| TutorialStep {
+            from,
+            to,
+            select_prompt,
+            pour_prompt,
+        };
+        game.tutorial = Some(Tutorial {
+            steps: vec![
+                step(
+                    0,
+                    2,
+                    "Welcome! Every stack should end up holding a single kind.\nSelect stack 1 by typing 1 and Enter",
+                    "Now pour it into the empty stack 3",
+                ),
+                step(
+                    1,
+                    0,
+                    "Units only go onto the same kind, or into an empty stack.\nSelect stack 2",
+                    "Now pour it onto the matching color in stack 1",
+                ),
+                step(
+                    1,
+                    2,
+                    "One more to go! Select stack 2 again",
+                    "Pour it onto stack 3 to finish the stage",
+                ),
+            ],
+        });
+        game
+    }
+
+    pub fn is_tutorial(&self) -> bool {
+        self.tutorial.is_some()
+    }
+
+    fn tutorial_step(&self) -> Option<&TutorialStep> {
+        self.tutorial.as_ref()?.steps.get(self.ledger.len())
+    }
+
+    pub fn tutorial_move(&self) -> Option<(usize, usize)> {
+        let step: &TutorialStep = self.tutorial_step()?;
+        Some((step.from, step.to))
+    }
+
+    // Outside a tutorial every move is accepted.
+    pub fn tutorial_accepts(&self, from: usize, to: usize) -> bool {
+        match self.tutorial_move() {
+            Some(expected) => expected == (from, to),
+            None => true,
+        }
+    }
+
+    // What to tell the player before their next input, pointing at the move through the hint
+    // highlight.
+    pub(crate) fn guide_tutorial(&mut self) {
+        let Some(step) = self.tutorial_step() else {
+            return;
+        };
+        let (prompt, from, to): (&str, usize, usize) = (step.select_prompt, step.from, step.to);
+        self.highlight = Some((from, to));
+        if self.message.is_none() {
+            self.message = Some(prompt.to_string());
+        }
+    }
+
+    // Only once the intended stack was selected, a wrong one is corrected after the pour.
+    pub(crate) fn tutorial_pour_prompt(&self, from: usize) -> Option<&'static str> {
+        self.tutorial_step()
+            .filter(|step| step.from == from)
+            .map(|step| step.pour_prompt)
+    }
+
+    pub(crate) fn correct_tutorial_move(&mut self) {
+        if let Some((from, to)) = self.tutorial_move() {
+            self.message = Some(format!(
+                "Not quite! This time, move stack {} to stack {}, they're marked on the board",
+                from + 1,
+                to + 1
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tutorial_accepts_only_the_intended_move() {
+        let mut game: Game = Game::tutorial();
+        assert!(game.tutorial_accepts(0, 2));
+        assert!(!game.tutorial_accepts(1, 2)); // Legal, but not what the step asks for.
+        assert!(!game.tutorial_accepts(2, 0));
+
+        while let Some((from, to)) = game.tutorial_move() {
+            assert_eq!(game.check_move(from, to), Ok(()));
+            game.move_legally(from, to);
+        }
+        assert!(game.stage_complete());
+        assert!(game.tutorial_accepts(0, 1)); // Once it's over, nothing is held back.
+        game.undo_move();
+        assert_eq!(game.tutorial_move(), Some((1, 2)));
+        assert!(!Game::get_stages()[0].is_tutorial());
+    }
+}
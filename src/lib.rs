@@ -1 +1 @@
-pub mod game;
\ No newline at end of file
+pub mod game;
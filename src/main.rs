@@ -1,5 +1,79 @@
-use sorting_game::game::Game;
+mod cli;
+
+use cli::{Args, Command};
+use sorting_game::game::{EmptyStackLook, Game, GameConfig, KeyBindings, RenderStyle, Settings};
+use std::env;
+use std::process;
+use std::sync::Arc;
 
 fn main() {
-    Game::play();
+    let args: Args = match cli::parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(reason) => exit_with_usage(&reason),
+    };
+    let settings: Settings = Settings {
+        style: match args.ascii {
+            true => RenderStyle::Ascii,
+            false => RenderStyle::Color,
+        },
+        palette: Arc::new(args.palette.with_user_config()),
+        explicit_moves: args.explicit_moves,
+        undo_limit: args.undo_limit,
+        quiet: args.quiet,
+        two_player: args.two_player,
+        no_sort: args.no_sort,
+        check_solvable: args.check_solvable,
+        cost_model: args.cost_model,
+        empty_look: match args.plain_empty {
+            true => EmptyStackLook::Plain,
+            false => EmptyStackLook::Dotted,
+        },
+        fast_forward: args.fast_forward,
+        compact_runs: args.compact,
+        face_down: args.face_down,
+        fast: args.fast,
+        dev: args.dev,
+        keys: match KeyBindings::load_user_config() {
+            Ok(keys) => Arc::new(keys),
+            Err(error) => {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+        },
+    };
+
+    match args.command {
+        Command::Menu => match Game::play(&settings) {
+            Ok(stats) if stats.stages_played > 0 => {
+                println!("{}", stats.summary(settings.style.honoring_no_color()))
+            }
+            Ok(_) => {}
+            Err(error) => {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+        },
+        Command::Help => println!("{}", cli::USAGE),
+        Command::Stage(path) => match Game::load_stage_file(&path) {
+            Ok(stage) => Game::play_single(stage, &settings),
+            Err(error) => {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+        },
+        Command::Generate {
+            seed,
+            kinds,
+            capacity,
+            empty_stacks,
+        } => match GameConfig::new(capacity, kinds, empty_stacks) {
+            Ok(config) => Game::play_single(Game::generate(&config, seed), &settings),
+            Err(reason) => exit_with_usage(&reason.to_string()),
+        },
+    }
+}
+
+fn exit_with_usage(reason: &str) -> ! {
+    eprintln!("{}\n\n{}", reason, cli::USAGE);
+    process::exit(2);
 }
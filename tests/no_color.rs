@@ -0,0 +1,17 @@
+// Kept in its own test binary, since setting NO_COLOR would leak into tests running alongside.
+use sorting_game::game::{Game, RenderStyle};
+
+#[test]
+fn test_no_color_overrides_color_style() {
+    std::env::set_var("NO_COLOR", "1");
+    let mut game: Game =
+        Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+    game.move_legally(0, 2);
+    game.move_legally(1, 0);
+
+    assert_eq!(RenderStyle::Color.honoring_no_color(), RenderStyle::Ascii);
+    let frame: String = game.render_to_string(RenderStyle::Color);
+    assert!(!frame.contains('\x1b'), "{:?}", frame);
+    assert!(frame.contains(" 1: |A A .| *"), "{}", frame);
+    assert!(frame.contains(" 3: |B . .|"), "{}", frame);
+}
@@ -0,0 +1,38 @@
+use sorting_game::game::{Game, MoveError};
+
+#[test]
+fn test_play_stage_to_completion() {
+    let mut game: Game = Game::parse_stage("name: Headless\n\nABA..\nBA.\nB.\n....\n").unwrap();
+    assert_eq!(game.get_stage_name(), "Headless");
+    assert!(!game.stage_complete());
+
+    while !game.stage_complete() {
+        let (from, to) = game.hint().expect("the stage is solvable");
+        assert!(game.legal_moves().contains(&(from, to)));
+        game.move_legally(from, to);
+    }
+    assert!(game
+        .get_stacks()
+        .iter()
+        .all(|stack| stack.is_vacant() || stack.holds_single_kind()));
+
+    let moves: usize = game.get_move_count();
+    game.undo_move();
+    assert!(!game.stage_complete());
+    assert_eq!(game.get_move_count(), moves - 1);
+}
+
+#[test]
+fn test_query_moves_without_changing_the_board() {
+    let game: Game = Game::new_from_vecs(vec![vec![1, 2, 0], vec![2, 1, 0], vec![0, 0, 0]], None);
+    assert_eq!(game.check_move(0, 1), Err(MoveError::TopMismatch));
+
+    let next: Game = game.simulate_move(0, 2).unwrap();
+    assert_eq!(next.get_move_count(), 1);
+    assert_eq!(game.get_move_count(), 0);
+    assert_eq!(game.get_stacks()[2].to_string(), "...");
+    assert_eq!(next.get_stacks()[2].get_top_unit_id(), 2);
+
+    let solution: Vec<(usize, usize)> = game.solve().unwrap();
+    assert_eq!(game.solve_astar().unwrap().len(), solution.len());
+}
@@ -0,0 +1,41 @@
+// Kept in its own test binary, since it points HOME at a scratch directory for the scores.
+use sorting_game::game::{Game, GameError, RenderStyle, SessionStats, Settings};
+
+fn settings() -> Settings {
+    Settings {
+        style: RenderStyle::Ascii,
+        quiet: true,
+        no_sort: true, // Rating every stage would only slow the test down.
+        ..Settings::default()
+    }
+}
+
+#[test]
+fn test_play_a_scripted_session() {
+    let home = std::env::temp_dir().join(format!("sorting-game-home-{}", std::process::id()));
+    std::fs::create_dir_all(&home).unwrap();
+    std::env::set_var("HOME", &home);
+
+    let solution: Vec<(usize, usize)> = Game::get_stages()[0].solve().unwrap();
+    let mut lines: Vec<String> = vec!["1".to_string(), String::new()]; // Past the intro.
+    lines.extend(
+        solution
+            .iter()
+            .map(|(from, to)| format!("{} {}", from + 1, to + 1)),
+    );
+    lines.push(String::new()); // Past the completion screen.
+    lines.push("q".to_string());
+
+    let stats: SessionStats = Game::play_with_script(&settings(), lines).unwrap();
+    assert_eq!(stats.stages_played, 1);
+    assert_eq!(stats.stages_completed, 1);
+    assert_eq!(stats.total_moves, solution.len());
+    assert_eq!(stats.total_undos, 0);
+
+    // Running out of input without quitting is reported to the caller.
+    let result: Result<SessionStats, GameError> =
+        Game::play_with_script(&settings(), vec!["1".to_string()]);
+    assert_eq!(result.unwrap_err(), GameError::InputClosed);
+
+    std::fs::remove_dir_all(&home).unwrap();
+}